@@ -1,5 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use rust_poly::{poly, Poly, Poly64, __util::casting::usize_to_scalar};
+use nalgebra::DMatrix;
+use num::complex::Complex64;
+use rust_poly::{poly, AllRootsAlgorithms, Poly, Poly64, __util::casting::usize_to_scalar};
 
 criterion_main!(micro_benches, realistic_benches);
 criterion_group!(
@@ -7,7 +9,9 @@ criterion_group!(
     bessel,
     reverse_bessel,
     legendre,
-    bench_usize_to_scalar
+    bench_usize_to_scalar,
+    eval_many,
+    structured_qr
 );
 
 pub fn bessel(c: &mut Criterion) {
@@ -49,6 +53,65 @@ pub fn legendre(c: &mut Criterion) {
     }
 }
 
+pub fn eval_many(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eval_many");
+    let p = Poly64::reverse_bessel(16).unwrap();
+    for n in [1_000, 100_000, 1_000_000] {
+        let xs: Vec<f64> = (0..n).map(|k| f64::from(k) / f64::from(n)).collect();
+        let zs: Vec<_> = xs.iter().map(|&x| Complex64::new(0.0, x)).collect();
+        // baselines: a 1x1 matrix per point is the allocating path used before
+        // `eval_many`, and `eval_point` is the same loop without allocations
+        group.bench_with_input(BenchmarkId::new("eval_1x1", n), &zs, |b, zs| {
+            b.iter(|| {
+                black_box(
+                    zs.iter()
+                        .map(|&z| p.eval(&DMatrix::from_row_slice(1, 1, &[z]))[0])
+                        .collect::<Vec<_>>(),
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("eval_point", n), &zs, |b, zs| {
+            b.iter(|| black_box(zs.iter().map(|&z| p.eval_point(z)).collect::<Vec<_>>()))
+        });
+        group.bench_with_input(BenchmarkId::new("eval_many", n), &zs, |b, zs| {
+            b.iter(|| black_box(p.eval_many(black_box(zs))))
+        });
+        group.bench_with_input(BenchmarkId::new("eval_in_place", n), &zs, |b, zs| {
+            let mut ys = zs.clone();
+            b.iter(|| {
+                ys.copy_from_slice(zs);
+                p.eval_in_place(black_box(&mut ys));
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("eval_many_real", n), &xs, |b, xs| {
+            b.iter(|| black_box(p.eval_many_real(black_box(xs))))
+        });
+    }
+    group.finish();
+}
+
+pub fn structured_qr(c: &mut Criterion) {
+    let mut group = c.benchmark_group("structured_qr");
+    group.sample_size(10);
+    for n in [8, 32, 128, 512, 2048, 10_000] {
+        let coeffs: Vec<f64> = (0..=n).map(|k| (f64::from(k) * 0.91).sin()).collect();
+        let p = Poly64::from_real_slice(&coeffs);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &p, |b, p| {
+            b.iter(|| {
+                black_box(p.try_roots(
+                    1E-14,
+                    100,
+                    1,
+                    None,
+                    Some(AllRootsAlgorithms::StructuredQR),
+                    None,
+                ))
+            })
+        });
+    }
+    group.finish();
+}
+
 criterion_group!(realistic_benches, bessel_filter_design);
 
 pub fn bessel_filter_design(c: &mut Criterion) {
@@ -5,9 +5,11 @@
 //! order. In this example, it's an order 48 filter.
 //!
 //! Then the roots are paired up in pairs, to form the denominator of the transfer
-//! functions of cascaded biquads. Note that we are skipping the stage of scaling
-//! the roots to obtain the required cutoff frequency. Here the cutoff is unscaled
-//! and implicit.
+//! functions of cascaded biquads. The polynomial has real coefficients, so
+//! `try_conjugate_roots` keeps the computation in real arithmetic and returns
+//! the complex roots as exact conjugate pairs, which makes every biquad real.
+//! Note that we are skipping the stage of scaling the roots to obtain the
+//! required cutoff frequency. Here the cutoff is unscaled and implicit.
 //!
 //! A full implementation would require additional steps for scaling and discretizing,
 //! those are skipped here.
@@ -20,17 +22,25 @@ fn main() {
         .unwrap()
         // epsilon is the largest allowed error in the roots
         //
-        // max_iter is the max number of iterations before giving up
-        //
-        // max_tries is how many times the solver can fail (run out of iterations)
-        // before giving up entirely. When the solver gets stuck, the problem
-        // is shrunk by finding a single root with a different method, this unstucks
-        // the solver for some problematic polynomials. In this case its not
-        // necessary so we put 1 try.
-        .try_roots(1E-14, 1000, 1, None, None, None)
+        // max_iter is the max number of iterations for each pair of roots
+        // before giving up
+        .try_conjugate_roots(1E-14, 100)
         .unwrap();
+    // each pair z, z* is the biquad x^2 - 2 Re(z) x + |z|^2
+    roots
+        .pairs
+        .iter()
+        .map(|z| poly![z.norm_sqr(), -2.0 * z.re, 1.0])
+        .for_each(|stage| println!("{stage}"));
+    // at such high orders, rounding errors can split a pair close to the real
+    // axis into two real roots, these are paired up into real biquads too,
+    // with a first order stage left over for odd orders
     roots
+        .real
         .chunks(2)
-        .map(|z| Poly64::from_roots(z))
+        .map(|r| {
+            r.iter()
+                .fold(poly![1.0], |stage, &r| stage * poly![-r, 1.0])
+        })
         .for_each(|stage| println!("{stage}"));
 }
@@ -1,9 +1,12 @@
 //! Internal utilities, not part of the API
 
 pub mod casting;
+pub mod companion_qr;
+pub mod compensated;
 pub mod complex;
 pub mod linalg;
 pub mod luts;
+pub mod real_qr;
 
 #[cfg(test)]
 pub(crate) mod test;
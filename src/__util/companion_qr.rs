@@ -0,0 +1,498 @@
+//! Structured QR algorithm for companion matrices.
+//!
+//! The companion matrix is never formed, instead it is kept factored as
+//! `A = Q R`, where `Q` is a descending sequence of core transformations and
+//! `R` is upper triangular and unitary-plus-rank-one, so `R` is itself
+//! represented by two sequences of core transformations `C` and `B`.
+//! This takes `O(n)` memory and `O(n)` time per QR iteration.
+//!
+//! Ref: J. L. Aurentz, T. Mach, R. Vandebril, D. S. Watkins "Fast and Backward
+//! Stable Computation of Roots of Polynomials"
+//! [DOI](https://doi.org/10.1137/140983434)
+
+use na::RealField;
+use num::{Complex, Float, One, Zero};
+
+use crate::{__util::linalg::eigen_2x2, Error, Scalar};
+
+/// A core transformation, the 2x2 unitary matrix `[c -conj(s); s conj(c)]`
+/// with determinant one, acting on two adjacent rows.
+#[derive(Clone, Copy, Debug)]
+struct Core<T> {
+    c: Complex<T>,
+    s: Complex<T>,
+}
+
+impl<T: Scalar + RealField + Float> Core<T> {
+    fn identity() -> Self {
+        Self {
+            c: Complex::one(),
+            s: Complex::zero(),
+        }
+    }
+
+    /// The core transformation with its first column parallel to `[x0; x1]`,
+    /// so that its adjoint maps `[x0; x1]` to `[r; 0]`
+    fn from_column(x0: Complex<T>, x1: Complex<T>) -> Self {
+        let r = Float::sqrt(x0.norm_sqr() + x1.norm_sqr());
+        if !r.is_normal() {
+            // the squares under- or overflowed
+            let r = Float::hypot(x0.norm(), x1.norm());
+            if r.is_zero() {
+                return Self::identity();
+            }
+            return Self {
+                c: x0.unscale(r),
+                s: x1.unscale(r),
+            };
+        }
+        let r_inv = Float::recip(r);
+        let (c, s) = (x0.scale(r_inv), x1.scale(r_inv));
+        // one Newton step towards unit norm, the accumulated loss of
+        // unitarity otherwise dominates the backward error
+        let half = T::from_f64(0.5).expect("infallible");
+        let three = T::from_u8(3).expect("infallible");
+        let scale = (three - c.norm_sqr() - s.norm_sqr()) * half;
+        Self {
+            c: c.scale(scale),
+            s: s.scale(scale),
+        }
+    }
+
+    fn adjoint(self) -> Self {
+        Self {
+            c: self.c.conj(),
+            s: -self.s,
+        }
+    }
+
+    /// Mirrors the core transformation, such that a core acting on rows
+    /// `(1, 2)` of a 3x3 matrix acts on rows `(1, 0)` instead.
+    fn flip(self) -> Self {
+        Self {
+            c: self.c.conj(),
+            s: -self.s.conj(),
+        }
+    }
+
+    /// The product `self * other` of two core transformations acting on the
+    /// same rows
+    fn fuse(self, other: Self) -> Self {
+        let (x0, x1) = self.apply(other.c, other.s);
+        // normalizing avoids drifting away from unitarity
+        Self::from_column(x0, x1)
+    }
+
+    fn apply(self, x0: Complex<T>, x1: Complex<T>) -> (Complex<T>, Complex<T>) {
+        (
+            self.c * x0 - self.s.conj() * x1,
+            self.s * x0 + self.c.conj() * x1,
+        )
+    }
+
+    /// Left-multiply rows `row` and `row + 1` of a column of length 3
+    fn apply_at(self, x: &mut [Complex<T>; 3], row: usize) {
+        (x[row], x[row + 1]) = self.apply(x[row], x[row + 1]);
+    }
+
+    /// Diagonal core transformation `diag(c, conj(c))`
+    fn is_diagonal(self) -> bool {
+        self.s.is_zero()
+    }
+}
+
+/// Given core transformations acting on rows `(0, 1)`, `(1, 2)` and `(0, 1)`,
+/// refactor their product into core transformations acting on rows `(1, 2)`,
+/// `(0, 1)` and `(1, 2)`.
+fn turnover_down<T: Scalar + RealField + Float>(
+    first: Core<T>,
+    middle: Core<T>,
+    last: Core<T>,
+) -> (Core<T>, Core<T>, Core<T>) {
+    // only the first two columns of the product are needed
+    let mut col0 = [Complex::one(), Complex::zero(), Complex::zero()];
+    let mut col1 = [Complex::zero(), Complex::one(), Complex::zero()];
+    for col in [&mut col0, &mut col1] {
+        last.apply_at(col, 0);
+        middle.apply_at(col, 1);
+        first.apply_at(col, 0);
+    }
+
+    let lower = Core::from_column(col0[1], col0[2]);
+    lower.adjoint().apply_at(&mut col0, 1);
+    lower.adjoint().apply_at(&mut col1, 1);
+    let upper = Core::from_column(col0[0], col0[1]);
+    upper.adjoint().apply_at(&mut col1, 0);
+
+    // the product is now `diag(1, F)`, since all factors have determinant one
+    (lower, upper, Core::from_column(col1[1], col1[2]))
+}
+
+/// Given core transformations acting on rows `(1, 2)`, `(0, 1)` and `(1, 2)`,
+/// refactor their product into core transformations acting on rows `(0, 1)`,
+/// `(1, 2)` and `(0, 1)`.
+fn turnover_up<T: Scalar + RealField + Float>(
+    first: Core<T>,
+    middle: Core<T>,
+    last: Core<T>,
+) -> (Core<T>, Core<T>, Core<T>) {
+    let (upper, lower, upper2) = turnover_down(first.flip(), middle.flip(), last.flip());
+    (upper.flip(), lower.flip(), upper2.flip())
+}
+
+/// Entry `(i, j)` of a descending sequence of core transformations, which is
+/// upper Hessenberg, for `i - 1 <= j <= i + 1`.
+fn descending_entry<T: Scalar + RealField + Float>(
+    cores: &[Core<T>],
+    i: usize,
+    j: usize,
+) -> Complex<T> {
+    let core = |k: Option<usize>| {
+        k.and_then(|k| cores.get(k).copied())
+            .unwrap_or_else(Core::identity)
+    };
+    let above = core(i.checked_sub(1));
+    if j + 1 == i {
+        above.s
+    } else if j == i {
+        above.c.conj() * core(Some(i)).c
+    } else {
+        debug_assert_eq!(j, i + 1);
+        -(above.c.conj() * core(Some(i)).s.conj() * core(Some(i + 1)).c)
+    }
+}
+
+/// The companion matrix `A = Q R` of size `n`, where `R` is the leading
+/// principal submatrix of the upper triangular matrix
+/// `R' = C^H (B + e_1 y^T)` of size `n + 1`.
+///
+/// `y` is never needed explicitly, because the entries of `R'` can be
+/// recovered from `C` and `B` alone, since `C R' = B + e_1 y^T` agrees with `B`
+/// everywhere except the first row.
+struct CompanionFactors<T> {
+    q: Vec<Core<T>>,
+    b: Vec<Core<T>>,
+    c: Vec<Core<T>>,
+}
+
+impl<T: Scalar + RealField + Float> CompanionFactors<T> {
+    /// `coeffs` are the coefficients of a monic polynomial, without the
+    /// leading one, lowest degree first.
+    #[allow(clippy::many_single_char_names)]
+    fn new(coeffs: &[Complex<T>]) -> Self {
+        let n = coeffs.len();
+        debug_assert!(n >= 2);
+
+        // the companion matrix with ones on the subdiagonal is the cyclic
+        // shift `Q` times `R`, which is the identity except for the last
+        // column, `R = U + x e_n^T` once padded with an extra zero row, where
+        // `U` only swaps the last two rows
+        let rotation = Core {
+            c: Complex::zero(),
+            s: Complex::one(),
+        };
+        let mut q = vec![rotation; n];
+        q[n - 1] = Core::identity();
+
+        let mut x: Vec<_> = coeffs[1..].iter().map(|a| -*a).collect();
+        x.push(if n.is_multiple_of(2) {
+            coeffs[0]
+        } else {
+            -coeffs[0]
+        });
+        x.push(-Complex::<T>::one());
+
+        // `C` maps `x` to a multiple of `e_1`, then `B = C U`
+        let mut c = vec![Core::identity(); n];
+        let mut tail = x[n];
+        for k in (0..n).rev() {
+            let core = Core::from_column(x[k], tail);
+            tail = core.adjoint().apply(x[k], tail).0;
+            c[k] = core.adjoint();
+        }
+        let mut b = c.clone();
+        b[n - 1] = c[n - 1].fuse(rotation);
+
+        Self { q, b, c }
+    }
+
+    /// Entry `(k, j)` of `R'`, for `k <= j <= k + 2`
+    fn r_entry(&self, k: usize, j: usize) -> Complex<T> {
+        if k >= self.c.len() {
+            // the padding row is zero
+            return Complex::zero();
+        }
+        let s = self.c[k].s;
+        match j - k {
+            0 => self.b[k].s / s,
+            1 => {
+                let bkk = descending_entry(&self.b, k + 1, k + 1);
+                let ckk = descending_entry(&self.c, k + 1, k + 1);
+                (bkk - ckk * self.r_entry(k + 1, k + 1)) / s
+            }
+            _ => {
+                debug_assert_eq!(j, k + 2);
+                let bkj = descending_entry(&self.b, k + 1, k + 2);
+                let ckk = descending_entry(&self.c, k + 1, k + 1);
+                let ckj = descending_entry(&self.c, k + 1, k + 2);
+                (bkj - ckk * self.r_entry(k + 1, k + 2) - ckj * self.r_entry(k + 2, k + 2)) / s
+            }
+        }
+    }
+
+    /// Entry `(i, j)` of the companion matrix, for `i - 1 <= j <= i + 1`
+    fn a_entry(&self, i: usize, j: usize) -> Complex<T> {
+        (i.saturating_sub(1)..=j).fold(Complex::zero(), |acc, k| {
+            acc + descending_entry(&self.q, i, k) * self.r_entry(k, j)
+        })
+    }
+
+    /// Computes `R' U = W R'` for the core transformation `U` acting on rows
+    /// `(i, i + 1)`, updating `R'` and returning `W`.
+    fn pass_through_r(&mut self, i: usize, u: Core<T>) -> Core<T> {
+        let (v, b0, b1) = turnover_down(self.b[i], self.b[i + 1], u);
+        self.b[i] = b0;
+        self.b[i + 1] = b1;
+        let (w, c1, c0) = turnover_up(self.c[i + 1].adjoint(), self.c[i].adjoint(), v);
+        self.c[i + 1] = c1.adjoint();
+        self.c[i] = c0.adjoint();
+        w
+    }
+
+    /// One implicit single shift QR iteration on the active block `lo..=hi`
+    fn sweep(&mut self, lo: usize, hi: usize, shift: Complex<T>) {
+        let u = Core::from_column(self.a_entry(lo, lo) - shift, self.a_entry(lo + 1, lo));
+
+        // apply `U^H` from the left, moving it past the diagonal core above
+        // the active block
+        let u_top = match lo.checked_sub(1) {
+            Some(k) => Core {
+                c: u.c,
+                s: self.q[k].c.conj() * u.s,
+            },
+            None => u,
+        };
+        self.q[lo] = u_top.adjoint().fuse(self.q[lo]);
+
+        // chase the misfit from the right, through `R` and then `Q`
+        let mut misfit = u;
+        for i in lo..hi {
+            let w = self.pass_through_r(i, misfit);
+            if i + 1 < hi {
+                let (x, q0, q1) = turnover_down(self.q[i], self.q[i + 1], w);
+                self.q[i] = q0;
+                self.q[i + 1] = q1;
+                misfit = x;
+            } else {
+                // move past the diagonal core below the active block and
+                // fuse, ending the iteration
+                let w_bottom = Core {
+                    c: w.c,
+                    s: self.q[hi].c * w.s,
+                };
+                self.q[hi - 1] = self.q[hi - 1].fuse(w_bottom);
+            }
+        }
+    }
+
+    /// Sets the core `Q_k` to be diagonal if it is negligible
+    fn try_deflate(&mut self, k: usize, tol: T) -> bool {
+        let core = &mut self.q[k];
+        if core.is_diagonal() {
+            return true;
+        }
+        if core.s.norm() > tol {
+            return false;
+        }
+        core.s = Complex::zero();
+        core.c = core.c.unscale(core.c.norm());
+        true
+    }
+}
+
+/// Roots of a monic polynomial, given its coefficients without the leading
+/// one, lowest degree first, using the structured single shift QR
+/// algorithm on its companion matrix.
+///
+/// `max_iter` is the maximum number of iterations per root.
+pub(crate) fn roots_companion_qr<T: Scalar + RealField + Float>(
+    coeffs: &[Complex<T>],
+    epsilon: T,
+    max_iter: usize,
+) -> Result<Vec<Complex<T>>, Error> {
+    // zero roots make the triangular factor singular, which the deflation
+    // criterion on the unitary factor cannot detect, so they are split off
+    let num_zeros = coeffs.iter().take_while(|c| c.is_zero()).count();
+    if num_zeros > 0 {
+        let mut roots = roots_companion_qr(&coeffs[num_zeros..], epsilon, max_iter)?;
+        roots.extend(std::iter::repeat_n(Complex::zero(), num_zeros));
+        return Ok(roots);
+    }
+
+    let n = coeffs.len();
+    match n {
+        0 => return Ok(vec![]),
+        1 => return Ok(vec![-coeffs[0]]),
+        _ => {}
+    }
+
+    let tol = Float::max(epsilon, T::epsilon());
+    let exceptional = T::from_f64(0.75).expect("infallible");
+    let mut factors = CompanionFactors::new(coeffs);
+    let mut roots = vec![Complex::zero(); n];
+
+    // the active block is `lo..=hi`
+    let mut hi = n - 1;
+    let mut iter = 0;
+    while hi > 0 {
+        let lo = (0..hi)
+            .rev()
+            .find(|&k| factors.try_deflate(k, tol))
+            .map_or(0, |k| k + 1);
+        if lo == hi {
+            roots[hi] = factors.a_entry(hi, hi);
+            hi -= 1;
+            iter = 0;
+            continue;
+        }
+        if iter >= max_iter {
+            return Err(Error::max_iter_inner());
+        }
+
+        let shift = if iter % 20 == 10 {
+            factors.a_entry(lo, lo) + factors.a_entry(lo + 1, lo).norm() * exceptional
+        } else if iter > 0 && iter % 20 == 0 {
+            factors.a_entry(hi, hi) + factors.a_entry(hi, hi - 1).norm() * exceptional
+        } else {
+            // Wilkinson shift
+            let last = factors.a_entry(hi, hi);
+            let (l1, l2) = eigen_2x2(
+                factors.a_entry(hi - 1, hi - 1),
+                factors.a_entry(hi - 1, hi),
+                factors.a_entry(hi, hi - 1),
+                last,
+            );
+            if (l1 - last).norm_sqr() <= (l2 - last).norm_sqr() {
+                l1
+            } else {
+                l2
+            }
+        };
+        factors.sweep(lo, hi, shift);
+        iter += 1;
+    }
+    roots[0] = factors.a_entry(0, 0);
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod test {
+    use num::complex::Complex64;
+
+    use super::{roots_companion_qr, turnover_down, turnover_up, Core};
+
+    fn core(c: Complex64, s: Complex64) -> Core<f64> {
+        Core::from_column(c, s)
+    }
+
+    /// Applies cores acting on rows `(0, 1)` or `(1, 2)` to the columns of
+    /// the 3x3 identity
+    fn product(cores: &[(Core<f64>, usize)]) -> [[Complex64; 3]; 3] {
+        let mut cols = [[Complex64::new(0.0, 0.0); 3]; 3];
+        for (k, col) in cols.iter_mut().enumerate() {
+            col[k] = Complex64::new(1.0, 0.0);
+            for &(core, row) in cores.iter().rev() {
+                core.apply_at(col, row);
+            }
+        }
+        cols
+    }
+
+    fn assert_same_product(lhs: [[Complex64; 3]; 3], rhs: [[Complex64; 3]; 3]) {
+        for (x, y) in lhs.iter().flatten().zip(rhs.iter().flatten()) {
+            assert!((x - y).norm() < 1E-14, "{x} != {y}");
+        }
+    }
+
+    #[test]
+    fn turnover() {
+        let a = core(Complex64::new(0.3, -0.2), Complex64::new(0.5, 0.1));
+        let b = core(Complex64::new(-0.7, 0.4), Complex64::new(0.2, 0.9));
+        let c = core(Complex64::new(0.1, 0.6), Complex64::new(-0.8, 0.3));
+
+        let (d, e, f) = turnover_down(a, b, c);
+        assert_same_product(
+            product(&[(a, 0), (b, 1), (c, 0)]),
+            product(&[(d, 1), (e, 0), (f, 1)]),
+        );
+
+        let (d, e, f) = turnover_up(a, b, c);
+        assert_same_product(
+            product(&[(a, 1), (b, 0), (c, 1)]),
+            product(&[(d, 0), (e, 1), (f, 0)]),
+        );
+    }
+
+    #[test]
+    fn quadratic() {
+        // z^2 - 3z + 2 = (z - 1)(z - 2)
+        let coeffs = [Complex64::new(2.0, 0.0), Complex64::new(-3.0, 0.0)];
+        let roots = roots_companion_qr(&coeffs, 1E-14, 100).unwrap();
+        assert!(roots.iter().any(|r| (r - 1.0).norm() < 1E-12));
+        assert!(roots.iter().any(|r| (r - 2.0).norm() < 1E-12));
+    }
+
+    #[test]
+    fn zero_roots() {
+        // z^4 + z^3 - 2z^2 = z^2 (z - 1) (z + 2)
+        let coeffs = [0.0, 0.0, -2.0, 1.0].map(|c| Complex64::new(c, 0.0));
+        let roots = roots_companion_qr(&coeffs, 1E-14, 100).unwrap();
+        assert_eq!(roots.iter().filter(|r| r.norm() == 0.0).count(), 2);
+        assert!(roots.iter().any(|r| (r - 1.0).norm() < 1E-12));
+        assert!(roots.iter().any(|r| (r + 2.0).norm() < 1E-12));
+    }
+
+    /// Checks that the backward error of the roots of a monic polynomial of
+    /// degree `n`, with deterministic coefficients of varying size and sign,
+    /// is below `tol`
+    fn check_high_degree(n: u32, tol: f64) {
+        let mut coeffs: Vec<_> = (0..n)
+            .map(|k| {
+                let t = f64::from(k);
+                Complex64::new((t * 0.91).sin(), (t * 0.27).cos())
+            })
+            .collect();
+        let roots = roots_companion_qr(&coeffs, 1E-14, 100).unwrap();
+        assert_eq!(roots.len(), coeffs.len());
+        coeffs.push(Complex64::new(1.0, 0.0));
+        for r in &roots {
+            // normwise backward error, evaluated at 1/r on the reversed
+            // polynomial outside the unit disk so that it doesn't overflow
+            let (x, coeffs) = if r.norm() > 1.0 {
+                (r.inv(), coeffs.clone())
+            } else {
+                (*r, coeffs.iter().rev().copied().collect())
+            };
+            let (mut value, mut scale) = (Complex64::new(0.0, 0.0), 0.0);
+            for c in &coeffs {
+                value = value * x + c;
+                scale = scale * x.norm() + c.norm();
+            }
+            assert!(value.norm() / scale < tol);
+        }
+    }
+
+    #[test]
+    #[ignore = "slow in debug builds"]
+    fn high_degree() {
+        // a modest multiple of n * eps
+        check_high_degree(2000, 1E-11);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn degree_10000() {
+        // the roots crowd the unit circle, where they are worse conditioned
+        check_high_degree(10_000, 1E-9);
+    }
+}
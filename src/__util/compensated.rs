@@ -0,0 +1,193 @@
+//! Error-free transformations and compensated polynomial evaluation, which
+//! give results as accurate as if computed in twice the working precision.
+//!
+//! Ref: S. Graillat, V. Ménissier-Morain "Accurate summation, dot product and
+//! polynomial evaluation in complex floating point arithmetic"
+//! [DOI](https://doi.org/10.1016/j.ic.2012.03.001)
+
+use num::{Complex, Float, Zero};
+
+use crate::Scalar;
+
+/// `a + b = s + e` exactly, where `s = fl(a + b)`
+pub(crate) fn two_sum<T: Float>(a: T, b: T) -> (T, T) {
+    let s = a + b;
+    let a_virtual = s - b;
+    let b_virtual = s - a_virtual;
+    (s, (a - a_virtual) + (b - b_virtual))
+}
+
+/// `a * b = p + e` exactly, where `p = fl(a * b)`
+pub(crate) fn two_prod<T: Float>(a: T, b: T) -> (T, T) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+/// `a + b = s + e` exactly, where `s = fl(a + b)`
+pub(crate) fn c_two_sum<T: Scalar + Float>(
+    a: Complex<T>,
+    b: Complex<T>,
+) -> (Complex<T>, Complex<T>) {
+    let (re, re_err) = two_sum(a.re, b.re);
+    let (im, im_err) = two_sum(a.im, b.im);
+    (Complex::new(re, im), Complex::new(re_err, im_err))
+}
+
+/// `a * b = p + e + f` exactly, where `p = fl(a * b)`, `e` are the errors of
+/// the products and `f` the errors of the sums
+pub(crate) fn c_two_prod<T: Scalar + Float>(
+    a: Complex<T>,
+    b: Complex<T>,
+) -> (Complex<T>, Complex<T>, Complex<T>) {
+    let (z1, h1) = two_prod(a.re, b.re);
+    let (z2, h2) = two_prod(a.im, b.im);
+    let (z3, h3) = two_prod(a.re, b.im);
+    let (z4, h4) = two_prod(a.im, b.re);
+    let (z5, h5) = two_sum(z1, -z2);
+    let (z6, h6) = two_sum(z3, z4);
+    (
+        Complex::new(z5, z6),
+        Complex::new(h1 - h2, h3 + h4),
+        Complex::new(h5, h6),
+    )
+}
+
+/// Compensated Horner scheme, `coeffs` are ordered from the lowest degree.
+///
+/// The relative error is bounded by about `eps + cond * eps^2`, where `cond`
+/// is the condition number of the evaluation, instead of `cond * eps`.
+pub(crate) fn horner_compensated<T: Scalar + Float>(
+    coeffs: &[Complex<T>],
+    x: Complex<T>,
+) -> Complex<T> {
+    horner_compensated_with_bound(coeffs, x).0
+}
+
+/// Compensated Horner scheme together with a running bound on the absolute
+/// error of the result, `coeffs` are ordered from the lowest degree.
+pub(crate) fn horner_compensated_with_bound<T: Scalar + Float>(
+    coeffs: &[Complex<T>],
+    x: Complex<T>,
+) -> (Complex<T>, T) {
+    let mut out = [Complex::zero()];
+    let bound = horner_compensated_derivatives(coeffs, x, &mut out);
+    (out[0], bound)
+}
+
+/// Compensated Horner scheme for the value, written to `out[0]`, in the same
+/// pass as plain Horner for the derivatives, written to the rest of `out`.
+/// Returns a running bound on the absolute error of the value.
+///
+/// The error-free transforms make `p(x)` exactly equal to the plain Horner
+/// value plus a correction polynomial in `x` whose coefficients are the
+/// rounding errors. The only errors left are those of evaluating the
+/// correction, which are bounded by `gamma_(4n+4)` times the correction
+/// evaluated on the moduli, and the final rounding.
+pub(crate) fn horner_compensated_derivatives<T: Scalar + Float>(
+    coeffs: &[Complex<T>],
+    x: Complex<T>,
+    out: &mut [Complex<T>],
+) -> T {
+    debug_assert!(!out.is_empty());
+
+    out.fill(Complex::zero());
+    let Some((&last, rest)) = coeffs.split_last() else {
+        return T::zero();
+    };
+    let x_abs = x.norm();
+    out[0] = last;
+    let (mut correction, mut magnitude) = (Complex::zero(), T::zero());
+    for &c in rest.iter().rev() {
+        for j in (1..out.len()).rev() {
+            out[j] = out[j] * x + out[j - 1];
+        }
+        let (prod, prod_err, sum_err) = c_two_prod(out[0], x);
+        let (value, err) = c_two_sum(prod, c);
+        out[0] = value;
+        correction = correction * x + (prod_err + sum_err + err);
+        magnitude = magnitude * x_abs + (prod_err.norm() + sum_err.norm() + err.norm());
+    }
+    out[0] = out[0] + correction;
+
+    // out[j] is the j-th Taylor coefficient, p^(j)(x) / j!
+    let mut j = T::zero();
+    let mut factorial = T::one();
+    for d in out.iter_mut().skip(1) {
+        j = j + T::one();
+        factorial = factorial * j;
+        *d = d.scale(factorial);
+    }
+
+    let u = T::epsilon() / (T::one() + T::one());
+    let k = T::from_usize(4 * coeffs.len()).expect("infallible") * u;
+    let gamma = k / (T::one() - k);
+    // the bound is itself computed in floating point, round it up
+    (gamma * magnitude + u * out[0].norm()) * (T::one() + k)
+}
+
+#[cfg(test)]
+mod test {
+    use num::complex::Complex64;
+
+    use super::{
+        horner_compensated, horner_compensated_derivatives, horner_compensated_with_bound,
+        two_prod, two_sum,
+    };
+
+    #[test]
+    fn error_free_transforms() {
+        let (s, e) = two_sum(1.0, 1E-20);
+        assert_eq!((s, e), (1.0, 1E-20));
+        let a = 1.0 + f64::EPSILON;
+        let (p, e) = two_prod(a, a);
+        assert_eq!(p, 1.0 + 2.0 * f64::EPSILON);
+        assert_eq!(e, f64::EPSILON * f64::EPSILON);
+    }
+
+    #[test]
+    fn compensated_horner_near_multiple_root() {
+        // (x - 1)^5, which is very ill-conditioned near 1
+        let coeffs = [-1.0, 5.0, -10.0, 10.0, -5.0, 1.0].map(|c| Complex64::new(c, 0.0));
+        let x = Complex64::new(1.0001, 0.0);
+        let exact = (x - 1.0).powi(5);
+        let naive = coeffs
+            .iter()
+            .rev()
+            .fold(Complex64::new(0.0, 0.0), |acc, c| acc * x + c);
+        let compensated = horner_compensated(&coeffs, x);
+        assert!((naive - exact).norm() > 1E-3 * exact.norm());
+        assert!((compensated - exact).norm() < 1E-9 * exact.norm());
+    }
+
+    #[test]
+    fn compensated_bound_contains_exact_value() {
+        // (x - 1)^5 near the root and (x - i)^3 at the root
+        let coeffs = [-1.0, 5.0, -10.0, 10.0, -5.0, 1.0].map(|c| Complex64::new(c, 0.0));
+        for x in [1.0001, 1.0 + 1E-7, 0.99999, 1.0] {
+            let x = Complex64::new(x, 0.0);
+            let (value, bound) = horner_compensated_with_bound(&coeffs, x);
+            assert!((value - (x - 1.0).powi(5)).norm() <= bound, "{x}");
+        }
+        let i = Complex64::i();
+        let coeffs = [
+            i,
+            Complex64::new(-3.0, 0.0),
+            -i.scale(3.0),
+            Complex64::new(1.0, 0.0),
+        ];
+        let (value, bound) = horner_compensated_with_bound(&coeffs, i);
+        assert!(value.norm() <= bound);
+    }
+
+    #[test]
+    fn compensated_derivatives() {
+        // (x - 1)^5 near the root, where the derivatives are well conditioned
+        let coeffs = [-1.0, 5.0, -10.0, 10.0, -5.0, 1.0].map(|c| Complex64::new(c, 0.0));
+        let x = Complex64::new(1.0001, 0.0);
+        let mut out = [Complex64::new(0.0, 0.0); 3];
+        let bound = horner_compensated_derivatives(&coeffs, x, &mut out);
+        assert_eq!((out[0], bound), horner_compensated_with_bound(&coeffs, x));
+        assert!((out[1] - (x - 1.0).powi(4) * 5.0).norm() < 1E-12);
+        assert!((out[2] - (x - 1.0).powi(3) * 20.0).norm() < 1E-12);
+    }
+}
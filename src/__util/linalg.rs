@@ -1,7 +1,7 @@
 use std::{
     cmp,
     hint::black_box,
-    ops::{Div, MulAssign},
+    ops::{Div, MulAssign, Range},
 };
 
 use na::{
@@ -11,9 +11,9 @@ use na::{
     RealField,
 };
 use nalgebra::DVector;
-use num::{complex::Complex64, Complex, One, Zero};
+use num::{complex::Complex64, Complex, Float, One, Zero};
 
-use crate::{Error, Scalar};
+use crate::{__util::complex::c_neg, Error, Scalar};
 
 pub(crate) fn convolve_1d<T: Scalar>(
     input: &DVector<Complex<T>>,
@@ -47,6 +47,23 @@ pub(crate) fn convolve_1d<T: Scalar>(
     output
 }
 
+/// The matrix of the linear map `q -> p * q`, where `q` has `cols`
+/// coefficients and `p` has coefficients `coeffs`, lowest degree first.
+pub(crate) fn convolution_matrix<T: Scalar + RealField>(
+    coeffs: &[Complex<T>],
+    cols: usize,
+) -> DMatrix<Complex<T>> {
+    debug_assert!(!coeffs.is_empty() && cols > 0);
+
+    let mut matrix = DMatrix::zeros(coeffs.len() + cols - 1, cols);
+    for j in 0..cols {
+        for (i, c) in coeffs.iter().enumerate() {
+            matrix[(i + j, j)] = c.clone();
+        }
+    }
+    matrix
+}
+
 /// Create a householder matrix from a column vector
 fn col_2_householder<T: Scalar + RealField>(mut col: DVector<Complex<T>>) -> DMatrix<Complex<T>> {
     type C<T> = Complex<T>;
@@ -105,8 +122,6 @@ fn upper_hessenberg<T: Scalar + RealField>(mut this: DMatrixViewMut<Complex<T>>)
 
     for i in 0..n - 2 {
         let mut h_holder_vec: DVector<_> = this.view((i + 1, i), (n - i - 1, 1)).column(0).into();
-        println!("{this}");
-        println!("{h_holder_vec}");
         col_2_householder_vec(&mut h_holder_vec);
 
         {
@@ -366,12 +381,783 @@ pub(crate) fn eigen_francis_shift<T: Scalar + RealField>(
     Ok(h.diagonal().as_slice().to_vec())
 }
 
+/// The 1-norm of a complex number, `|re| + |im|`, which is cheaper than the
+/// modulus and just as good for comparisons.
+fn cabs1<T: Float>(z: Complex<T>) -> T {
+    z.re.abs() + z.im.abs()
+}
+
+/// Complex Givens rotation, returns `(c, s)` such that
+/// `[c s; -conj(s) c] * [f; g] = [r; 0]`, like LAPACK's `zlartg`.
+///
+/// Ref: D. Bindel, J. Demmel, W. Kahan, O. Marques "On Computing Givens Rotations
+/// Reliably and Efficiently" [DOI](https://doi.org/10.1145/567806.567809)
+fn givens<T: Scalar + RealField + Float>(f: Complex<T>, g: Complex<T>) -> (T, Complex<T>) {
+    if g.is_zero() {
+        return (T::one(), Complex::zero());
+    }
+    if f.is_zero() {
+        return (T::zero(), g.conj().unscale(g.norm()));
+    }
+    let f_abs = f.norm();
+    let hyp = Float::hypot(f_abs, g.norm());
+    (f_abs / hyp, f.unscale(f_abs) * g.conj().unscale(hyp))
+}
+
+/// Apply a Givens rotation to a pair of vectors, like BLAS's `zrot`.
+fn rot<T: Scalar + RealField + Float>(x: &mut Complex<T>, y: &mut Complex<T>, c: T, s: Complex<T>) {
+    let tmp = x.scale(c) + s * *y;
+    *y = y.scale(c) - s.conj() * *x;
+    *x = tmp;
+}
+
+/// Apply a Givens rotation from the left to the rows `k` and `k + 1`,
+/// restricted to the columns in `cols`.
+fn rot_rows<T: Scalar + RealField + Float>(
+    h: &mut DMatrixViewMut<Complex<T>>,
+    k: usize,
+    cols: Range<usize>,
+    (c, s): (T, Complex<T>),
+) {
+    for j in cols {
+        let (mut upper, mut lower) = (h[(k, j)], h[(k + 1, j)]);
+        rot(&mut upper, &mut lower, c, s);
+        h[(k, j)] = upper;
+        h[(k + 1, j)] = lower;
+    }
+}
+
+/// Apply the conjugate transpose of a Givens rotation from the right to the
+/// columns `k` and `k + 1`, restricted to the rows in `rows`.
+fn rot_cols<T: Scalar + RealField + Float>(
+    h: &mut DMatrixViewMut<Complex<T>>,
+    k: usize,
+    rows: Range<usize>,
+    (c, s): (T, Complex<T>),
+) {
+    for i in rows {
+        let (mut left, mut right) = (h[(i, k)], h[(i, k + 1)]);
+        rot(&mut left, &mut right, c, s.conj());
+        h[(i, k)] = left;
+        h[(i, k + 1)] = right;
+    }
+}
+
+/// Turns `x` into a normalized Householder vector `u` in-place, such that
+/// `(I - 2uu^H)x` is a multiple of the first unit vector.
+///
+/// Returns `false` if `x` is already a multiple of the first unit vector, in
+/// which case no reflection is necessary.
+fn householder_vec<T: Scalar + RealField + Float>(x: &mut [Complex<T>]) -> bool {
+    let tail_sq = x[1..].iter().fold(T::zero(), |acc, z| acc + z.norm_sqr());
+    if tail_sq.is_zero() {
+        return false;
+    }
+    let head = x[0].norm();
+    let norm = Float::sqrt(head * head + tail_sq);
+    let phase = if head.is_zero() {
+        Complex::one()
+    } else {
+        x[0].unscale(head)
+    };
+    x[0] += phase.scale(norm);
+    let u_norm = Float::sqrt(T::from_u8(2).expect("infallible") * norm * (norm + head));
+    for z in x.iter_mut() {
+        *z = z.unscale(u_norm);
+    }
+    true
+}
+
+/// Apply the reflector `I - 2uu^H` from the left to the rows starting at `row`
+/// and to the columns in `cols`.
+fn reflect_left<T: Scalar + RealField + Float>(
+    h: &mut DMatrixViewMut<Complex<T>>,
+    u: &[Complex<T>],
+    row: usize,
+    cols: Range<usize>,
+) {
+    let two = T::from_u8(2).expect("infallible");
+    for j in cols {
+        let dot = u
+            .iter()
+            .enumerate()
+            .fold(Complex::<T>::zero(), |acc, (i, ui)| {
+                acc + ui.conj() * h[(row + i, j)]
+            });
+        let dot = dot.scale(two);
+        for (i, ui) in u.iter().enumerate() {
+            h[(row + i, j)] -= *ui * dot;
+        }
+    }
+}
+
+/// Apply the reflector `I - 2uu^H` from the right to the columns starting at
+/// `col` and to the rows in `rows`.
+fn reflect_right<T: Scalar + RealField + Float>(
+    h: &mut DMatrixViewMut<Complex<T>>,
+    u: &[Complex<T>],
+    col: usize,
+    rows: Range<usize>,
+) {
+    let two = T::from_u8(2).expect("infallible");
+    for i in rows {
+        let dot = u
+            .iter()
+            .enumerate()
+            .fold(Complex::<T>::zero(), |acc, (j, uj)| {
+                acc + h[(i, col + j)] * *uj
+            });
+        let dot = dot.scale(two);
+        for (j, uj) in u.iter().enumerate() {
+            h[(i, col + j)] -= dot * uj.conj();
+        }
+    }
+}
+
+/// Eigenvalues of a 2x2 matrix `[a b; c d]`
+pub(crate) fn eigen_2x2<T: Scalar + RealField + Float>(
+    a: Complex<T>,
+    b: Complex<T>,
+    c: Complex<T>,
+    d: Complex<T>,
+) -> (Complex<T>, Complex<T>) {
+    let half = T::from_f64(0.5).expect("infallible");
+    let mean = (a + d).scale(half);
+    let diff = (a - d).scale(half);
+    let off_diag = b * c;
+    let disc = (diff * diff + off_diag).sqrt();
+    let (l1, l2) = (mean + disc, mean - disc);
+
+    // avoid cancellation by computing the smaller eigenvalue from the determinant
+    let (big, small) = if l1.norm_sqr() >= l2.norm_sqr() {
+        (l1, l2)
+    } else {
+        (l2, l1)
+    };
+    if big.is_zero() {
+        return (big, small);
+    }
+    (big, (a * d - b * c) / big)
+}
+
+/// Thresholds used for deciding when entries are negligible
+#[derive(Clone, Copy)]
+struct Tolerance<T> {
+    ulp: T,
+    small_num: T,
+}
+
+impl<T: Scalar + RealField + Float> Tolerance<T> {
+    fn new(epsilon: T, n: usize) -> Self {
+        let ulp = Float::max(epsilon, T::epsilon());
+        let n = T::from_usize(n).expect("infallible");
+        Self {
+            ulp,
+            small_num: T::min_positive_value() * (n / ulp),
+        }
+    }
+
+    /// The subdiagonal entry `h[k, k-1]` of the Hessenberg matrix is
+    /// negligible, using the criterion by Ahues and Tisseur, like in LAPACK.
+    fn is_negligible(self, h: &DMatrixViewMut<Complex<T>>, k: usize, lo: usize, hi: usize) -> bool {
+        let sub = cabs1(h[(k, k - 1)]);
+        if sub <= self.small_num {
+            return true;
+        }
+        let mut test = cabs1(h[(k - 1, k - 1)]) + cabs1(h[(k, k)]);
+        if test.is_zero() {
+            if k >= lo + 2 {
+                test += cabs1(h[(k - 1, k - 2)]);
+            }
+            if k < hi {
+                test += cabs1(h[(k + 1, k)]);
+            }
+        }
+        if sub > self.ulp * test {
+            return false;
+        }
+        let sup = cabs1(h[(k - 1, k)]);
+        let ab = Float::max(sub, sup);
+        let ba = Float::min(sub, sup);
+        let diag = cabs1(h[(k - 1, k - 1)] - h[(k, k)]);
+        let diag_k = cabs1(h[(k, k)]);
+        let aa = Float::max(diag_k, diag);
+        let bb = Float::min(diag_k, diag);
+        let s = aa + ab;
+        ba * (ab / s) <= Float::max(self.small_num, self.ulp * (bb * (aa / s)))
+    }
+}
+
+/// Diagonal similarity scaling of a square matrix, like LAPACK's `zgebal`
+/// (without permutations).
+///
+/// Unlike [`balance_matrix`], this never gives up on badly scaled matrices,
+/// such as companion matrices of polynomials with large coefficients.
+fn balance_matrix_unbounded<T: Scalar + RealField + Float>(mut this: DMatrixViewMut<Complex<T>>) {
+    // TODO: tune this to input size
+    const MAX_ITER: usize = 100;
+
+    let n = this.nrows();
+    debug_assert_eq!(n, this.ncols(), "matrix must be square");
+
+    let radix = T::from_u8(2).expect("infallible");
+    let factor = T::from_f64(0.95).expect("infallible");
+    let sfmin = T::min_positive_value() / T::epsilon() * radix;
+    let sfmax = Float::recip(sfmin);
+
+    for _ in 0..MAX_ITER {
+        let mut converged = true;
+        for i in 0..n {
+            let mut col_norm = Float::sqrt(
+                (0..n)
+                    .filter(|&k| k != i)
+                    .fold(T::zero(), |acc, k| acc + this[(k, i)].norm_sqr()),
+            );
+            let mut row_norm = Float::sqrt(
+                (0..n)
+                    .filter(|&k| k != i)
+                    .fold(T::zero(), |acc, k| acc + this[(i, k)].norm_sqr()),
+            );
+            if col_norm.is_zero() || row_norm.is_zero() {
+                continue;
+            }
+            let total = col_norm + row_norm;
+            let mut scale = T::one();
+            while col_norm < row_norm / radix
+                && Float::max(scale, col_norm) < sfmax / radix
+                && row_norm > sfmin * radix
+            {
+                scale *= radix;
+                col_norm *= radix;
+                row_norm /= radix;
+            }
+            while col_norm >= row_norm * radix
+                && Float::max(row_norm, scale) < sfmax / radix
+                && Float::min(col_norm, scale) > sfmin * radix
+            {
+                scale /= radix;
+                col_norm /= radix;
+                row_norm *= radix;
+            }
+            if col_norm + row_norm >= factor * total {
+                continue;
+            }
+            converged = false;
+            for k in 0..n {
+                this[(i, k)] = this[(i, k)].unscale(scale);
+                this[(k, i)] = this[(k, i)].scale(scale);
+            }
+        }
+        if converged {
+            return;
+        }
+    }
+}
+
+/// Single-shift complex QR iteration on the upper Hessenberg matrix `h`,
+/// like LAPACK's `zlahqr`.
+///
+/// On success, `h` is in Schur form and, if provided, the Schur vectors are
+/// accumulated into `q`.
+fn schur_single_shift<T: Scalar + RealField + Float>(
+    h: &mut DMatrixViewMut<Complex<T>>,
+    mut q: Option<&mut DMatrixViewMut<Complex<T>>>,
+    tol: Tolerance<T>,
+    max_iter_per_deflation: usize,
+) -> Result<(), Error> {
+    let n = h.nrows();
+    if n == 0 {
+        return Ok(());
+    }
+    let exceptional = T::from_f64(0.75).expect("infallible");
+
+    // the active block is `lo..=hi`
+    let mut hi = n - 1;
+    let mut iter = 0;
+    while hi > 0 {
+        let mut lo = 0;
+        for k in (1..=hi).rev() {
+            if tol.is_negligible(h, k, 0, hi) {
+                h[(k, k - 1)] = Complex::zero();
+                lo = k;
+                break;
+            }
+        }
+        if lo == hi {
+            // a 1x1 block has converged
+            hi -= 1;
+            iter = 0;
+            continue;
+        }
+        if iter >= max_iter_per_deflation {
+            return Err(Error::max_iter_inner());
+        }
+
+        let shift = if iter % 20 == 10 {
+            h[(lo, lo)] + Float::abs(h[(lo + 1, lo)].re) * exceptional
+        } else if iter > 0 && iter % 20 == 0 {
+            h[(hi, hi)] + Float::abs(h[(hi, hi - 1)].re) * exceptional
+        } else {
+            // Wilkinson shift, the eigenvalue of the trailing 2x2 block
+            // closest to the last diagonal entry
+            let (l1, l2) = eigen_2x2(
+                h[(hi - 1, hi - 1)],
+                h[(hi - 1, hi)],
+                h[(hi, hi - 1)],
+                h[(hi, hi)],
+            );
+            if (l1 - h[(hi, hi)]).norm_sqr() <= (l2 - h[(hi, hi)]).norm_sqr() {
+                l1
+            } else {
+                l2
+            }
+        };
+
+        // chase the bulge from the top of the active block to the bottom
+        for k in lo..hi {
+            let rotation = if k == lo {
+                givens(h[(lo, lo)] - shift, h[(lo + 1, lo)])
+            } else {
+                givens(h[(k, k - 1)], h[(k + 1, k - 1)])
+            };
+            let first_col = if k == lo { k } else { k - 1 };
+            rot_rows(h, k, first_col..n, rotation);
+            if k > lo {
+                h[(k + 1, k - 1)] = Complex::zero();
+            }
+            rot_cols(h, k, 0..(k + 3).min(hi + 1), rotation);
+            if let Some(q) = q.as_mut() {
+                let rows = 0..q.nrows();
+                rot_cols(q, k, rows, rotation);
+            }
+        }
+        iter += 1;
+    }
+    Ok(())
+}
+
+/// Swap the adjacent diagonal entries `k` and `k + 1` of the upper triangular
+/// matrix `t`, updating the Schur vectors `q`, like LAPACK's `ztrexc`.
+fn swap_schur<T: Scalar + RealField + Float>(
+    t: &mut DMatrixViewMut<Complex<T>>,
+    q: &mut DMatrixViewMut<Complex<T>>,
+    k: usize,
+) {
+    let n = t.nrows();
+    let t11 = t[(k, k)];
+    let t22 = t[(k + 1, k + 1)];
+    let rotation = givens(t[(k, k + 1)], t22 - t11);
+    rot_rows(t, k, k + 2..n, rotation);
+    rot_cols(t, k, 0..k, rotation);
+    t[(k, k)] = t22;
+    t[(k + 1, k + 1)] = t11;
+    let rows = 0..q.nrows();
+    rot_cols(q, k, rows, rotation);
+}
+
+/// Aggressive early deflation on the trailing `nw` x `nw` window of the
+/// active block `lo..=hi`, like LAPACK's `zlaqr3`.
+///
+/// Returns the number of deflated eigenvalues, which are left at the bottom
+/// of the active block, and the undeflated eigenvalues of the window, which
+/// make good shifts.
+///
+/// Ref: K. Braman, R. Byers, R. Mathias "The Multishift QR Algorithm. Part II:
+/// Aggressive Early Deflation" [DOI](https://doi.org/10.1137/S0895479801384585)
+fn aggressive_early_deflation<T: Scalar + RealField + Float>(
+    h: &mut DMatrixViewMut<Complex<T>>,
+    lo: usize,
+    hi: usize,
+    nw: usize,
+    tol: Tolerance<T>,
+    max_iter_per_deflation: usize,
+) -> (usize, Vec<Complex<T>>) {
+    let top = hi + 1 - nw;
+    let spike = if top == lo {
+        Complex::zero()
+    } else {
+        h[(top, top - 1)]
+    };
+
+    // Schur decomposition of the window
+    let mut t = h.view((top, top), (nw, nw)).clone_owned();
+    for j in 0..nw {
+        for i in j + 2..nw {
+            t[(i, j)] = Complex::zero();
+        }
+    }
+    let mut q = DMatrix::<Complex<T>>::identity(nw, nw);
+    let mut t_view = t.as_view_mut();
+    let mut q_view = q.as_view_mut();
+    if schur_single_shift(&mut t_view, Some(&mut q_view), tol, max_iter_per_deflation).is_err() {
+        // no deflation is possible, but the eigenvalues of the window are
+        // still good shifts
+        let shifts = h.view((top, top), (nw, nw)).diagonal().as_slice().to_vec();
+        return (0, shifts);
+    }
+
+    // check for deflation from the bottom of the window, moving undeflatable
+    // eigenvalues to the top
+    let mut undeflated = nw;
+    let mut first = 0;
+    while first < undeflated {
+        let k = undeflated - 1;
+        let mut magnitude = cabs1(t_view[(k, k)]);
+        if magnitude.is_zero() {
+            magnitude = cabs1(spike);
+        }
+        if cabs1(spike) * cabs1(q_view[(0, k)]) <= Float::max(tol.small_num, tol.ulp * magnitude) {
+            undeflated -= 1;
+        } else {
+            for j in (first..k).rev() {
+                swap_schur(&mut t_view, &mut q_view, j);
+            }
+            first += 1;
+        }
+    }
+    let shifts = (0..undeflated).map(|k| t_view[(k, k)]).collect();
+    let deflated = nw - undeflated;
+    if deflated == 0 && !spike.is_zero() {
+        return (0, shifts);
+    }
+
+    // the spike is the first column of the window before the Schur
+    // decomposition, after it it becomes the first row of Q
+    let mut spike_vec: Vec<_> = (0..nw)
+        .map(|k| {
+            if k < undeflated {
+                spike * q_view[(0, k)].conj()
+            } else {
+                Complex::zero()
+            }
+        })
+        .collect();
+
+    // restore the Hessenberg form of the undeflated part
+    if !spike.is_zero() && undeflated > 1 {
+        let mut u = spike_vec[..undeflated].to_vec();
+        if householder_vec(&mut u) {
+            reflect_left(&mut t_view, &u, 0, 0..nw);
+            reflect_right(&mut t_view, &u, 0, 0..nw);
+            reflect_right(&mut q_view, &u, 0, 0..nw);
+            let norm = Float::sqrt(
+                spike_vec[..undeflated]
+                    .iter()
+                    .fold(T::zero(), |acc, z| acc + z.norm_sqr()),
+            );
+            let phase = if spike_vec[0].is_zero() {
+                Complex::one()
+            } else {
+                spike_vec[0].unscale(spike_vec[0].norm())
+            };
+            spike_vec[0] = c_neg(phase.scale(norm));
+            for z in &mut spike_vec[1..undeflated] {
+                *z = Complex::zero();
+            }
+        }
+        for j in 0..undeflated.saturating_sub(2) {
+            let mut u: Vec<_> = (j + 1..undeflated).map(|i| t_view[(i, j)]).collect();
+            if householder_vec(&mut u) {
+                reflect_left(&mut t_view, &u, j + 1, j..nw);
+                reflect_right(&mut t_view, &u, j + 1, 0..undeflated);
+                reflect_right(&mut q_view, &u, j + 1, 0..nw);
+            }
+            for i in j + 2..undeflated {
+                t_view[(i, j)] = Complex::zero();
+            }
+        }
+    }
+
+    // copy the window back and update the rest of the active block
+    h.view_mut((top, top), (nw, nw)).copy_from(&t);
+    if top > lo {
+        for (k, z) in spike_vec.into_iter().enumerate() {
+            h[(top + k, top - 1)] = z;
+        }
+        let updated = h.view((lo, top), (top - lo, nw)) * &q;
+        h.view_mut((lo, top), (top - lo, nw)).copy_from(&updated);
+    }
+    (deflated, shifts)
+}
+
+/// Chase a chain of tightly packed 3x3 bulges through the active block
+/// `lo..=hi`, two shifts per bulge, like LAPACK's `zlaqr5`.
+///
+/// Ref: K. Braman, R. Byers, R. Mathias "The Multishift QR Algorithm. Part I:
+/// Maintaining Well-Focused Shifts and Level 3 Performance"
+/// [DOI](https://doi.org/10.1137/S0895479801384573)
+fn multishift_sweep<T: Scalar + RealField + Float>(
+    h: &mut DMatrixViewMut<Complex<T>>,
+    lo: usize,
+    hi: usize,
+    shifts: &[Complex<T>],
+) {
+    debug_assert!(hi >= lo + 2, "bulges need at least a 3x3 block");
+    debug_assert!(shifts.len() >= 2);
+
+    let num_bulges = shifts.len() / 2;
+
+    // bulge `m` is introduced at step `3m`, all bulges move one row down at
+    // each step, deeper bulges are moved first
+    let steps = hi - lo + 3 * num_bulges - 2;
+    for step in 0..steps {
+        for m in 0..num_bulges {
+            let Some(pos) = step.checked_sub(3 * m) else {
+                break;
+            };
+            // the bulge is in rows `k + 1..=k + 3`, or it is being introduced
+            // at the top of the active block if `pos == 0`
+            let k = lo + pos;
+            if k > hi - 1 {
+                continue;
+            }
+            if pos == 0 {
+                let (s1, s2) = (shifts[2 * m], shifts[2 * m + 1]);
+                let mut x = first_column_double_shift(h, lo, s1, s2);
+                if householder_vec(&mut x) {
+                    reflect_left(h, &x, lo, lo..hi + 1);
+                    reflect_right(h, &x, lo, lo..(lo + 4).min(hi + 1));
+                }
+            } else if k + 2 <= hi {
+                let mut x = [h[(k, k - 1)], h[(k + 1, k - 1)], h[(k + 2, k - 1)]];
+                if householder_vec(&mut x) {
+                    reflect_left(h, &x, k, k - 1..hi + 1);
+                    reflect_right(h, &x, k, lo..(k + 4).min(hi + 1));
+                }
+                h[(k + 1, k - 1)] = Complex::zero();
+                h[(k + 2, k - 1)] = Complex::zero();
+            } else {
+                // the bulge is at the bottom, so only a 2x2 reflector fits
+                let mut x = [h[(k, k - 1)], h[(k + 1, k - 1)]];
+                if householder_vec(&mut x) {
+                    reflect_left(h, &x, k, k - 1..hi + 1);
+                    reflect_right(h, &x, k, lo..hi + 1);
+                }
+                h[(k + 1, k - 1)] = Complex::zero();
+            }
+        }
+    }
+}
+
+/// First column of `(H - s1 I)(H - s2 I)`, scaled to avoid overflows, like
+/// LAPACK's `zlaqr1`.
+fn first_column_double_shift<T: Scalar + RealField + Float>(
+    h: &DMatrixViewMut<Complex<T>>,
+    lo: usize,
+    s1: Complex<T>,
+    s2: Complex<T>,
+) -> [Complex<T>; 3] {
+    let scale = cabs1(h[(lo, lo)] - s2) + cabs1(h[(lo + 1, lo)]);
+    if scale.is_zero() {
+        return [Complex::zero(); 3];
+    }
+    let h10 = h[(lo + 1, lo)].unscale(scale);
+    [
+        h10 * h[(lo, lo + 1)] + (h[(lo, lo)] - s1) * (h[(lo, lo)] - s2).unscale(scale),
+        h10 * (h[(lo, lo)] + h[(lo + 1, lo + 1)] - s1 - s2),
+        h10 * h[(lo + 2, lo + 1)],
+    ]
+}
+
+/// Small-bulge multishift QR algorithm with aggressive early deflation, for
+/// upper Hessenberg matrices.
+///
+/// This is the algorithm used by LAPACK's `zlaqr0`, computing only the
+/// eigenvalues.
+pub(crate) fn eigen_multishift_qr<T: Scalar + RealField + Float>(
+    mut this: DMatrixViewMut<Complex<T>>,
+    epsilon: T,
+    max_iter: usize,
+    max_iter_per_deflation: usize,
+) -> Result<Vec<Complex<T>>, Error> {
+    // TODO: tune these to the input, these are the defaults of LAPACK's `iparmq`
+    // blocks smaller than this use the single-shift algorithm
+    const NMIN: usize = 75;
+    // skip the QR sweep if AED deflated more than this percentage of the window
+    const NIBBLE: usize = 14;
+    // use exceptional shifts after this many iterations without deflation
+    const KEXSH: usize = 6;
+
+    let n = this.nrows();
+    debug_assert_eq!(n, this.ncols(), "matrix must be square");
+    debug_assert!(
+        (0..n).all(|j| (j + 2..n).all(|i| this[(i, j)].is_zero())),
+        "matrix must be upper Hessenberg"
+    );
+
+    balance_matrix_unbounded(this.as_view_mut());
+    let mut h = this;
+    let tol = Tolerance::new(epsilon, n);
+    let mut eigs = vec![Complex::zero(); n];
+
+    // the active block is `lo..=hi`, everything below it has been deflated
+    let Some(mut hi) = n.checked_sub(1) else {
+        return Ok(eigs);
+    };
+    // keeps track of total (outer) iterations
+    let mut niter = 0;
+    // keeps track of total (inner) iterations per each deflation step
+    let mut piter = 0;
+    loop {
+        let mut lo = 0;
+        for k in (1..=hi).rev() {
+            if tol.is_negligible(&h, k, 0, hi) {
+                h[(k, k - 1)] = Complex::zero();
+                lo = k;
+                break;
+            }
+        }
+        let nh = hi - lo + 1;
+
+        if nh <= 2 {
+            if nh == 1 {
+                eigs[hi] = h[(hi, hi)];
+            } else {
+                (eigs[lo], eigs[hi]) =
+                    eigen_2x2(h[(lo, lo)], h[(lo, hi)], h[(hi, lo)], h[(hi, hi)]);
+            }
+            piter = 0;
+            if lo == 0 {
+                return Ok(eigs);
+            }
+            hi = lo - 1;
+            continue;
+        }
+
+        if nh < NMIN {
+            let mut block = h.view((lo, lo), (nh, nh)).clone_owned();
+            schur_single_shift(&mut block.as_view_mut(), None, tol, max_iter_per_deflation)?;
+            eigs[lo..=hi].copy_from_slice(block.diagonal().as_slice());
+            piter = 0;
+            if lo == 0 {
+                return Ok(eigs);
+            }
+            hi = lo - 1;
+            continue;
+        }
+
+        // give up if didn't converge
+        if niter >= max_iter {
+            return Err(Error::max_iter_user());
+        }
+        if piter >= max_iter_per_deflation {
+            return Err(Error::max_iter_inner());
+        }
+        niter += 1;
+        piter += 1;
+
+        let num_shifts = recommended_num_shifts(nh);
+        let nw = if nh <= 500 {
+            num_shifts
+        } else {
+            num_shifts * 3 / 2
+        }
+        .min(nh - 1);
+
+        let (deflated, aed_shifts) =
+            aggressive_early_deflation(&mut h, lo, hi, nw, tol, max_iter_per_deflation);
+        if deflated > 0 {
+            for k in hi + 1 - deflated..=hi {
+                eigs[k] = h[(k, k)];
+            }
+            hi -= deflated;
+            piter = 0;
+            if deflated * 100 > nw * NIBBLE || hi < lo + 2 {
+                continue;
+            }
+        }
+
+        let num_shifts = num_shifts.min(hi - lo - 1);
+        let shifts = select_shifts(&h, hi, num_shifts, &aed_shifts, piter % KEXSH == 0);
+        multishift_sweep(&mut h, lo, hi, &shifts);
+    }
+}
+
+/// Pick an even number of shifts for the next sweep on the active block
+/// ending at `hi`, from the shifts found during AED, or exceptional shifts
+/// to break cycles.
+fn select_shifts<T: Scalar + RealField + Float>(
+    h: &DMatrixViewMut<Complex<T>>,
+    hi: usize,
+    num_shifts: usize,
+    aed_shifts: &[Complex<T>],
+    exceptional: bool,
+) -> Vec<Complex<T>> {
+    let mut shifts: Vec<_> = if exceptional {
+        let factor = T::from_f64(0.75).expect("infallible");
+        (0..num_shifts / 2)
+            .map(|i| {
+                let k = hi - 2 * i;
+                h[(k, k)] + cabs1(h[(k, k - 1)]) * factor
+            })
+            .flat_map(|s| [s, s])
+            .collect()
+    } else {
+        let start = aed_shifts.len().saturating_sub(num_shifts);
+        aed_shifts[start..].to_vec()
+    };
+    shifts.truncate(shifts.len() - shifts.len() % 2);
+    if shifts.len() < 2 {
+        let (l1, l2) = eigen_2x2(
+            h[(hi - 1, hi - 1)],
+            h[(hi - 1, hi)],
+            h[(hi, hi - 1)],
+            h[(hi, hi)],
+        );
+        shifts = vec![l1, l2];
+    }
+    if shifts.len() == 2 {
+        // use the shift closest to the last diagonal entry twice
+        let last = h[(hi, hi)];
+        let closest = if cabs1(shifts[0] - last) < cabs1(shifts[1] - last) {
+            shifts[0]
+        } else {
+            shifts[1]
+        };
+        shifts = vec![closest, closest];
+    }
+    shifts
+}
+
+/// The number of simultaneous shifts, from LAPACK's `iparmq`
+fn recommended_num_shifts(nh: usize) -> usize {
+    let ns = match nh {
+        0..=29 => 2,
+        30..=59 => 4,
+        60..=149 => 10,
+        150..=589 => 10.max(nh / nh.ilog2() as usize),
+        590..=2999 => 64,
+        3000..=5999 => 128,
+        _ => 256,
+    };
+    2.max(ns - ns % 2)
+}
+
 #[cfg(test)]
 mod test {
     use na::{dmatrix, matrix};
     use num::complex::{Complex64, ComplexFloat};
 
-    use super::{balance_matrix, eigen_francis_shift};
+    use na::DMatrix;
+
+    use super::{
+        aggressive_early_deflation, balance_matrix, eigen_francis_shift, eigen_multishift_qr,
+        multishift_sweep, Tolerance,
+    };
+
+    /// An upper Hessenberg matrix with distinct diagonal entries and
+    /// subdiagonal entries of size `sub`, the rest is deterministic noise
+    fn hessenberg(n: usize, sub: f64) -> DMatrix<Complex64> {
+        DMatrix::from_fn(n, n, |i, j| {
+            let t = (i * n + j) as f64;
+            match (i, j) {
+                _ if i == j => Complex64::new(i as f64, (t * 0.7).sin()),
+                _ if i == j + 1 => Complex64::new(sub, 0.0),
+                _ if i < j => Complex64::new((t * 1.3).sin(), (t * 0.4).cos()),
+                _ => Complex64::new(0.0, 0.0),
+            }
+        })
+    }
 
     #[test]
     fn test_balance_matrix() {
@@ -396,4 +1182,82 @@ mod test {
         assert!(eigs.iter().any(|x| (x - eig_2).abs() < 1e-4));
         assert!(eigs.iter().any(|x| (x - eig_3).abs() < 1e-4));
     }
+
+    #[test]
+    fn test_eigen_multishift_qr_3x3() {
+        let mut m =
+            dmatrix![17.0, 22.0, 27.0; 22.0, 29.0, 36.0; 0.0, 36.0, 45.0].cast::<Complex64>();
+        let expected = m.clone().eigenvalues().unwrap();
+        let eigs = eigen_multishift_qr(m.as_view_mut(), 1E-12, 100, 100).unwrap();
+        for e in expected.iter() {
+            assert!(eigs.iter().any(|x| (x - e).abs() < 1e-8));
+        }
+    }
+
+    #[test]
+    fn eigen_multishift_qr_large_companion() {
+        // z^150 - 1, above the size where the multishift sweeps and AED
+        // take over from the single-shift algorithm
+        let n = 150;
+        let mut m = DMatrix::<Complex64>::zeros(n, n);
+        m[(0, n - 1)] = Complex64::new(1.0, 0.0);
+        for i in 1..n {
+            m[(i, i - 1)] = Complex64::new(1.0, 0.0);
+        }
+        let eigs = eigen_multishift_qr(m.as_view_mut(), 1E-14, 1000, 1000).unwrap();
+        assert_eq!(eigs.len(), n);
+        for k in 0..n {
+            let exact = Complex64::from_polar(1.0, std::f64::consts::TAU * k as f64 / n as f64);
+            assert!(eigs.iter().any(|z| (z - exact).abs() < 1E-12));
+        }
+        for z in &eigs {
+            // residual of z^n - 1, scaled by the sum of the moduli of the terms
+            assert!((z.powu(150) - 1.0).abs() / 2.0 < 1E-12);
+        }
+    }
+
+    #[test]
+    fn aggressive_early_deflation_large_window() {
+        // weakly coupled diagonal blocks, so the bottom of the window deflates
+        // although no subdiagonal entry is negligible on its own
+        let n = 120;
+        let mut h = hessenberg(n, 1E-3);
+        let mut expected: Vec<_> = h.clone().eigenvalues().unwrap().iter().copied().collect();
+        let tol = Tolerance::new(1E-14, n);
+        let (deflated, shifts) =
+            aggressive_early_deflation(&mut h.as_view_mut(), 0, n - 1, 30, tol, 1000);
+        assert!(deflated > 0);
+        assert_eq!(deflated + shifts.len(), 30);
+        for k in n - deflated..n {
+            assert!(h[(k, k - 1)].abs() == 0.0 || k == n - deflated);
+            let i = (0..expected.len())
+                .min_by(|&a, &b| {
+                    (expected[a] - h[(k, k)])
+                        .abs()
+                        .total_cmp(&(expected[b] - h[(k, k)]).abs())
+                })
+                .unwrap();
+            assert!((expected.swap_remove(i) - h[(k, k)]).abs() < 1E-10);
+        }
+    }
+
+    #[test]
+    fn multishift_sweep_is_a_similarity() {
+        let n = 100;
+        let mut h = hessenberg(n, 0.5);
+        let (trace, norm) = (h.trace(), h.norm());
+        let shifts: Vec<_> = (0..10)
+            .map(|k| Complex64::new(f64::from(k) * 9.0, 1.0))
+            .collect();
+        multishift_sweep(&mut h.as_view_mut(), 0, n - 1, &shifts);
+        // unitary similarity transforms keep the trace and the Frobenius norm,
+        // and the bulges are chased all the way out
+        assert!((h.trace() - trace).abs() < 1E-10 * norm);
+        assert!((h.norm() - norm).abs() < 1E-10 * norm);
+        for j in 0..n {
+            for i in j + 2..n {
+                assert!(h[(i, j)].abs() < 1E-12 * norm);
+            }
+        }
+    }
 }
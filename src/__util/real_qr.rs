@@ -0,0 +1,320 @@
+//! Real double-shift Francis QR for real upper Hessenberg matrices, which
+//! finds complex eigenvalues as exact conjugate pairs without ever leaving real
+//! arithmetic.
+//!
+//! This is the algorithm of EISPACK's `hqr`, computing only the eigenvalues.
+//!
+//! Ref: R. S. Martin, G. Peters, J. H. Wilkinson "The QR algorithm for real
+//! Hessenberg matrices" [DOI](https://doi.org/10.1007/BF02219770)
+
+use na::{DMatrix, RealField};
+use num::{Complex, Float};
+
+use crate::{Error, Scalar};
+
+/// Use exceptional shifts after this many iterations without deflation
+const EXCEPTIONAL_SHIFT: usize = 10;
+
+/// Balances the matrix in place with diagonal similarity transforms by powers
+/// of two, which do not introduce rounding errors, so that the norms of
+/// every row and its corresponding column are roughly equal.
+///
+/// Rows and columns with infinite or NaN norms are left alone, and the
+/// scaling factors stay within the range of floating point numbers.
+///
+/// Ref: B. N. Parlett, C. Reinsch "Balancing a matrix for calculation of
+/// eigenvalues and eigenvectors" [DOI](https://doi.org/10.1007/BF02165404)
+///
+/// # Errors
+/// If the matrix is not balanced after `max_iter` sweeps.
+pub(crate) fn balance_real<T: Scalar + RealField + Float>(
+    a: &mut DMatrix<T>,
+    max_iter: usize,
+) -> Result<(), Error> {
+    let n = a.nrows();
+    let radix = T::from_u8(2).expect("infallible");
+    let radix_sqr = radix * radix;
+    let threshold = T::from_f64(0.95).expect("infallible");
+    let sfmin = T::min_positive_value() / T::epsilon() * radix;
+    let sfmax = Float::recip(sfmin);
+
+    for _ in 0..max_iter {
+        let mut done = true;
+        for i in 0..n {
+            let (mut col_norm, row_norm) = (0..n).filter(|&j| j != i).fold(
+                (T::zero(), T::zero()),
+                |(col_norm, row_norm), j| {
+                    (
+                        col_norm + Float::abs(a[(j, i)]),
+                        row_norm + Float::abs(a[(i, j)]),
+                    )
+                },
+            );
+            if col_norm.is_zero()
+                || row_norm.is_zero()
+                || !Float::is_finite(col_norm)
+                || !Float::is_finite(row_norm)
+            {
+                continue;
+            }
+            let total = col_norm + row_norm;
+            let mut scale = T::one();
+            while col_norm < row_norm / radix && scale < sfmax / radix {
+                scale *= radix;
+                col_norm *= radix_sqr;
+            }
+            while col_norm > row_norm * radix && scale > sfmin * radix {
+                scale /= radix;
+                col_norm /= radix_sqr;
+            }
+            if (col_norm + row_norm) / scale < threshold * total {
+                done = false;
+                a.row_mut(i).scale_mut(Float::recip(scale));
+                a.column_mut(i).scale_mut(scale);
+            }
+        }
+        if done {
+            return Ok(());
+        }
+    }
+    Err(Error::max_iter_outer())
+}
+
+/// The eigenvalues of a real upper Hessenberg matrix, as real eigenvalues and
+/// complex eigenvalues with positive imaginary part, each standing for a
+/// conjugate pair.
+///
+/// `max_iter` is the maximum number of iterations per deflation.
+#[allow(clippy::many_single_char_names)]
+#[allow(clippy::too_many_lines)]
+pub(crate) fn eigen_real_double_shift<T: Scalar + RealField + Float>(
+    mut a: DMatrix<T>,
+    epsilon: T,
+    max_iter: usize,
+) -> Result<(Vec<T>, Vec<Complex<T>>), Error> {
+    let n = a.nrows();
+    debug_assert_eq!(n, a.ncols(), "matrix must be square");
+
+    let half = Float::recip(T::from_u8(2).expect("infallible"));
+    let tol = Float::max(epsilon, T::epsilon());
+    let mut real = Vec::with_capacity(n);
+    let mut pairs = Vec::with_capacity(n / 2);
+
+    // used when a subdiagonal element is compared against a zero diagonal
+    let norm = (0..n)
+        .flat_map(|i| (i.saturating_sub(1)..n).map(move |j| (i, j)))
+        .fold(T::zero(), |acc, ij| acc + Float::abs(a[ij]));
+
+    // the active block is `lo..=hi`, `shift` is the sum of the exceptional
+    // shifts that have been subtracted from the diagonal
+    let mut shift = T::zero();
+    let mut hi = n;
+    let mut iter = 0;
+    while hi > 0 {
+        let last = hi - 1;
+
+        // look for a single small subdiagonal element
+        let mut lo = last;
+        while lo > 0 {
+            let mut s = Float::abs(a[(lo - 1, lo - 1)]) + Float::abs(a[(lo, lo)]);
+            if s.is_zero() {
+                s = norm;
+            }
+            if Float::abs(a[(lo, lo - 1)]) <= tol * s {
+                a[(lo, lo - 1)] = T::zero();
+                break;
+            }
+            lo -= 1;
+        }
+
+        let mut x = a[(last, last)];
+        if lo == last {
+            real.push(x + shift);
+            hi -= 1;
+            iter = 0;
+            continue;
+        }
+        let mut y = a[(last - 1, last - 1)];
+        let mut w = a[(last, last - 1)] * a[(last - 1, last)];
+        if lo + 1 == last {
+            // the eigenvalues of the trailing 2x2 block
+            let p = (y - x) * half;
+            let q = p * p + w;
+            let z = Float::sqrt(Float::abs(q));
+            x += shift;
+            if q >= T::zero() {
+                let z = p + Float::abs(z) * Float::signum(p);
+                real.push(x + z);
+                real.push(if z.is_zero() { x + z } else { x - w / z });
+            } else {
+                pairs.push(Complex::new(x + p, z));
+            }
+            hi -= 2;
+            iter = 0;
+            continue;
+        }
+
+        if iter >= max_iter {
+            return Err(Error::max_iter_inner());
+        }
+        if iter > 0 && iter.is_multiple_of(EXCEPTIONAL_SHIFT) {
+            shift += x;
+            for i in 0..hi {
+                a[(i, i)] -= x;
+            }
+            let s = Float::abs(a[(last, last - 1)]) + Float::abs(a[(last - 1, last - 2)]);
+            x = T::from_f64(0.75).expect("infallible") * s;
+            y = x;
+            w = T::from_f64(-0.4375).expect("infallible") * s * s;
+        }
+        iter += 1;
+
+        // form the shift and look for two consecutive small subdiagonal
+        // elements, to start the bulge as low as possible
+        let mut m = last - 2;
+        let (mut p, mut q, mut r);
+        loop {
+            let z = a[(m, m)];
+            let r1 = x - z;
+            let s1 = y - z;
+            p = (r1 * s1 - w) / a[(m + 1, m)] + a[(m, m + 1)];
+            q = a[(m + 1, m + 1)] - z - r1 - s1;
+            r = a[(m + 2, m + 1)];
+            let s = Float::abs(p) + Float::abs(q) + Float::abs(r);
+            p /= s;
+            q /= s;
+            r /= s;
+            if m == lo {
+                break;
+            }
+            let u = Float::abs(a[(m, m - 1)]) * (Float::abs(q) + Float::abs(r));
+            let v = Float::abs(p)
+                * (Float::abs(a[(m - 1, m - 1)]) + Float::abs(z) + Float::abs(a[(m + 1, m + 1)]));
+            if u <= T::epsilon() * v {
+                break;
+            }
+            m -= 1;
+        }
+        for i in m + 2..hi {
+            a[(i, i - 2)] = T::zero();
+            if i != m + 2 {
+                a[(i, i - 3)] = T::zero();
+            }
+        }
+
+        // double shift QR step on rows `lo..=last` and columns `m..=last`
+        for k in m..last {
+            if k != m {
+                p = a[(k, k - 1)];
+                q = a[(k + 1, k - 1)];
+                r = if k + 1 == last {
+                    T::zero()
+                } else {
+                    a[(k + 2, k - 1)]
+                };
+                x = Float::abs(p) + Float::abs(q) + Float::abs(r);
+                if !x.is_zero() {
+                    p /= x;
+                    q /= x;
+                    r /= x;
+                }
+            }
+            let s = Float::sqrt(p * p + q * q + r * r) * Float::signum(p);
+            if s.is_zero() {
+                continue;
+            }
+            if k == m {
+                if lo != m {
+                    a[(k, k - 1)] = -a[(k, k - 1)];
+                }
+            } else {
+                a[(k, k - 1)] = -s * x;
+            }
+            p += s;
+            x = p / s;
+            y = q / s;
+            let z = r / s;
+            q /= p;
+            r /= p;
+            // row modification
+            for j in k..hi {
+                let mut p = a[(k, j)] + q * a[(k + 1, j)];
+                if k + 1 != last {
+                    p += r * a[(k + 2, j)];
+                    a[(k + 2, j)] -= p * z;
+                }
+                a[(k + 1, j)] -= p * y;
+                a[(k, j)] -= p * x;
+            }
+            // column modification
+            for i in lo..=last.min(k + 3) {
+                let mut p = x * a[(i, k)] + y * a[(i, k + 1)];
+                if k + 1 != last {
+                    p += z * a[(i, k + 2)];
+                    a[(i, k + 2)] -= p * r;
+                }
+                a[(i, k + 1)] -= p * q;
+                a[(i, k)] -= p;
+            }
+        }
+    }
+    Ok((real, pairs))
+}
+
+#[cfg(test)]
+mod test {
+    use na::DMatrix;
+
+    use super::{balance_real, eigen_real_double_shift};
+
+    #[test]
+    fn rotation_and_scaling() {
+        // a rotation by 90 degrees scaled by 2, and a real eigenvalue 3
+        let m =
+            DMatrix::<f64>::from_row_slice(3, 3, &[0.0, -2.0, 1.0, 2.0, 0.0, 5.0, 0.0, 0.0, 3.0]);
+        let (real, pairs) = eigen_real_double_shift(m, 1E-14, 100).unwrap();
+        assert_eq!(real.len(), 1);
+        assert!((real[0] - 3.0).abs() < 1E-14);
+        assert_eq!(pairs.len(), 1);
+        assert!(pairs[0].re.abs() < 1E-14);
+        assert!((pairs[0].im - 2.0).abs() < 1E-14);
+    }
+
+    #[test]
+    fn balancing_terminates() {
+        // infinite and NaN entries are left alone instead of being scaled
+        // forever
+        let mut m = DMatrix::from_row_slice(
+            3,
+            3,
+            &[1.0, f64::INFINITY, 0.0, 1.0, 2.0, f64::NAN, 0.0, 1.0, 3.0],
+        );
+        assert!(balance_real(&mut m, 100).is_ok());
+
+        // the scaling needed exceeds the range of floating point numbers
+        let mut m = DMatrix::<f64>::from_row_slice(2, 2, &[0.0, 1E300, 1E-300, 0.0]);
+        assert!(balance_real(&mut m, 100).is_ok());
+        assert!(m.iter().all(|x| x.is_finite()));
+
+        // a single sweep is not enough for this one
+        let mut m = DMatrix::from_row_slice(3, 3, &[1.0, 1E6, 0.0, 1E-6, 2.0, 1E6, 0.0, 1E-6, 3.0]);
+        assert!(balance_real(&mut m, 1).is_err());
+    }
+
+    #[test]
+    fn balancing_preserves_eigenvalues() {
+        // similar to a symmetric matrix with eigenvalues 2 - sqrt(3), 2 and
+        // 2 + sqrt(3)
+        let mut m = DMatrix::from_row_slice(3, 3, &[1.0, 1E6, 0.0, 1E-6, 2.0, 1E6, 0.0, 1E-6, 3.0]);
+        let before = m.clone();
+        balance_real(&mut m, 100).unwrap();
+        assert!(m.norm() < before.norm());
+        let (mut real, pairs) = eigen_real_double_shift(m, 1E-14, 100).unwrap();
+        assert!(pairs.is_empty());
+        real.sort_by(f64::total_cmp);
+        let sqrt3 = 3.0f64.sqrt();
+        for (a, b) in real.iter().zip(&[2.0 - sqrt3, 2.0, 2.0 + sqrt3]) {
+            assert!((a - b).abs() < 1E-14);
+        }
+    }
+}
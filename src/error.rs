@@ -21,6 +21,34 @@ pub(crate) enum ErrorKind {
     #[error("did not converge")]
     Pathological,
 
+    /// Use this when a bracketing method is given an interval at whose ends
+    /// the function has the same sign
+    #[error("the polynomial does not change sign in the given interval")]
+    NoSignChange,
+
+    /// Use this when a contour passes through a root, or so close to it that
+    /// the argument of the polynomial is unreliable
+    #[error("a root lies on or too close to the contour")]
+    RootOnContour,
+
+    /// Use this when an operation that requires a square matrix is given a
+    /// rectangular one
+    #[error("the matrix is not square")]
+    NotSquare,
+
+    /// Use this when a bounded interval or region is given an infinite or
+    /// NaN bound
+    #[error("the bounds must be finite")]
+    NotFinite,
+
+    /// Use this when a tolerance is zero, negative, infinite or NaN
+    #[error("the tolerance must be positive and finite")]
+    InvalidTolerance,
+
+    /// Use this when a disk is given a negative radius
+    #[error("the radius must not be negative")]
+    NegativeRadius,
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -63,6 +91,42 @@ impl Error {
         }
     }
 
+    pub(crate) fn no_sign_change() -> Self {
+        Self {
+            source: ErrorKind::NoSignChange,
+        }
+    }
+
+    pub(crate) fn root_on_contour() -> Self {
+        Self {
+            source: ErrorKind::RootOnContour,
+        }
+    }
+
+    pub(crate) fn not_square() -> Self {
+        Self {
+            source: ErrorKind::NotSquare,
+        }
+    }
+
+    pub(crate) fn not_finite() -> Self {
+        Self {
+            source: ErrorKind::NotFinite,
+        }
+    }
+
+    pub(crate) fn invalid_tolerance() -> Self {
+        Self {
+            source: ErrorKind::InvalidTolerance,
+        }
+    }
+
+    pub(crate) fn negative_radius() -> Self {
+        Self {
+            source: ErrorKind::NegativeRadius,
+        }
+    }
+
     /// Maps [`ErrorKind::MaxIterOuter`] to [`ErrorKind::MaxIterInner`]
     pub(crate) fn map_inner(self) -> Self {
         match self.source {
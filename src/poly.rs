@@ -7,17 +7,39 @@ use nalgebra::RealField;
 use num::{traits::real::Real, Complex, Float, One, Zero};
 
 use crate::{
-    Scalar, ScalarOps,
-    __util::complex::{c_neg, complex_fmt, complex_sort_mut},
+    Error, Scalar, ScalarOps,
+    __util::{
+        complex::{c_neg, complex_fmt, complex_sort_mut},
+        compensated::horner_compensated_with_bound,
+    },
 };
 
 mod base;
+mod bounds;
 mod calculus;
+mod condition;
 mod conversions;
 mod impl_num;
 mod indexing;
+mod polish;
+mod real_roots;
+mod regions;
+mod root_finder;
 mod roots;
 mod special_funcs;
+mod stability;
+
+pub use bounds::{RootAnnulus, RootBounds};
+pub use polish::{PolishAlgorithms, PolishedRoot};
+pub use real_roots::ConjugateRoots;
+pub use regions::RootRect;
+pub use root_finder::{FoundRoot, RootFinder, RootReport, RootSource};
+pub use roots::{AllRootsAlgorithms, InclusionDisk, OneRootAlgorithms};
+pub use stability::{RouthArray, SchurCohn, StabilityCounts};
+
+/// Number of points evaluated together in each Horner step by
+/// [`Poly::eval_many`] and its variants, so the loop over them vectorizes
+const EVAL_LANES: usize = 8;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Poly<T: Scalar>(pub(crate) na::DVector<Complex<T>>);
@@ -267,23 +289,95 @@ impl<T: Scalar + PartialOrd> Poly<T> {
 }
 
 impl<T: ScalarOps> Poly<T> {
-    /// Evaluate the polynomial for each entry of a matrix.
+    /// Evaluate the polynomial for each entry of a matrix of any shape.
+    ///
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use nalgebra::DMatrix;
+    /// use num::Complex;
+    ///
+    /// let p: Poly<f64> = poly![1.0, 0.0, 1.0];
+    /// let x = DMatrix::from_row_slice(1, 3, &[0.0, 1.0, 2.0].map(|x| Complex::new(x, 0.0)));
+    /// let y = DMatrix::from_row_slice(1, 3, &[1.0, 2.0, 5.0].map(|x| Complex::new(x, 0.0)));
+    /// assert_eq!(p.eval(&x), y);
+    /// ```
     #[must_use]
     pub fn eval(&self, x: &na::DMatrix<Complex<T>>) -> na::DMatrix<Complex<T>> {
-        let mut c0: na::DMatrix<_> = na::DMatrix::<_>::from_element(
-            x.nrows(),
-            x.ncols(),
-            // FIXME: panics when len is zero, should check for it
-            self.0[self.len_raw() - 1].clone(),
-        );
-        for i in 2..=self.len_raw() {
-            c0 = c0.clone() * x.clone();
-            c0.apply(|c| *c = (*c).clone() + &self.0[self.len_raw() - i]);
+        x.map(|x| self.eval_point(x))
+    }
+
+    /// Evaluate the matrix polynomial `p(A) = a_0 I + a_1 A + ... + a_n A^n`
+    /// of a square matrix.
+    ///
+    /// The Paterson-Stockmeyer scheme splits the polynomial into blocks of
+    /// `s = ceil(sqrt(n))` coefficients, computes `A^2, ..., A^s` once and
+    /// evaluates the blocks with Horner's method in `A^s`, which takes about
+    /// `2 sqrt(n)` matrix products instead of `n`.
+    ///
+    /// Ref: M. S. Paterson, L. J. Stockmeyer "On the Number of Nonscalar
+    /// Multiplications Necessary to Evaluate Polynomials"
+    /// [DOI](https://doi.org/10.1137/0202007)
+    ///
+    /// # Errors
+    /// If the matrix is not square.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use nalgebra::DMatrix;
+    /// use num::Complex;
+    ///
+    /// // the characteristic polynomial annihilates the matrix
+    /// let a = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 3.0, 4.0].map(|x| Complex::new(x, 0.0)));
+    /// let p: Poly<f64> = poly![-2.0, -5.0, 1.0];
+    /// assert_eq!(p.eval_matrix(&a).unwrap(), DMatrix::zeros(2, 2));
+    /// assert!(p.eval_matrix(&DMatrix::zeros(2, 3)).is_err());
+    /// ```
+    pub fn eval_matrix(
+        &self,
+        a: &na::DMatrix<Complex<T>>,
+    ) -> Result<na::DMatrix<Complex<T>>, Error> {
+        if !a.is_square() {
+            return Err(Error::not_square());
+        }
+        let n = a.nrows();
+        let Some(degree) = self.len_raw().checked_sub(1) else {
+            return Ok(na::DMatrix::zeros(n, n));
+        };
+
+        let mut s = 1;
+        while s * s < degree {
+            s += 1;
         }
-        c0
+        // A^0, ..., A^(s - 1), and A^s if there is more than one block
+        let mut powers = vec![na::DMatrix::identity(n, n)];
+        for _ in 1..s.min(degree + 1) {
+            powers.push(&powers[powers.len() - 1] * a);
+        }
+        let block = |k: usize| {
+            self.0
+                .iter()
+                .skip(k * s)
+                .take(s)
+                .zip(&powers)
+                .fold(na::DMatrix::zeros(n, n), |acc, (c, power)| {
+                    acc + power * c.clone()
+                })
+        };
+
+        let blocks = degree / s;
+        let mut result = block(blocks);
+        if blocks > 0 {
+            let a_s = &powers[s - 1] * a;
+            for k in (0..blocks).rev() {
+                result = &result * &a_s + block(k);
+            }
+        }
+        Ok(result)
     }
 
-    /// Evaluate the polynomial at a single value of `x`.
+    /// Evaluate the polynomial at a single value of `x` with Horner's method,
+    /// without allocating.
     ///
     /// ```
     /// use rust_poly::Poly;
@@ -293,8 +387,146 @@ impl<T: ScalarOps> Poly<T> {
     /// let x = Complex::new(1.0, 0.0);
     /// assert_eq!(p.eval_point(x), Complex::new(6.0, 0.0));
     /// ```
+    #[allow(clippy::needless_pass_by_value)]
     pub fn eval_point(&self, x: Complex<T>) -> Complex<T> {
-        self.eval(&na::DMatrix::<_>::from_row_slice(1, 1, &[x]))[0].clone()
+        self.0
+            .iter()
+            .rev()
+            .fold(Complex::zero(), |acc, c| acc * x.clone() + c)
+    }
+
+    /// Evaluate the polynomial at a single real value of `x`, like
+    /// [`Poly::eval_point`] but with real multiplications.
+    ///
+    /// ```
+    /// use rust_poly::Poly;
+    /// use num::Complex;
+    ///
+    /// let p = Poly::new(&[Complex::new(1.0, 1.0), Complex::new(2.0, 0.0), Complex::new(3.0, 0.0)]);
+    /// assert_eq!(p.eval_point_real(2.0), Complex::new(17.0, 1.0));
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn eval_point_real(&self, x: T) -> Complex<T> {
+        self.0
+            .iter()
+            .rev()
+            .fold(Complex::zero(), |acc, c| acc.scale(x.clone()) + c)
+    }
+
+    /// Evaluate the polynomial at many values of `x`, several of them at once
+    /// in each Horner step so the compiler can vectorize the loop.
+    ///
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use num::Complex;
+    ///
+    /// let p: Poly<f64> = poly![1.0, 0.0, 1.0];
+    /// let xs: Vec<_> = (0..10).map(|k| Complex::new(k as f64, 1.0)).collect();
+    /// let ys = p.eval_many(&xs);
+    /// assert!(xs.iter().zip(ys).all(|(&x, y)| p.eval_point(x) == y));
+    /// ```
+    #[must_use]
+    pub fn eval_many(&self, xs: &[Complex<T>]) -> Vec<Complex<T>> {
+        let mut ys = xs.to_vec();
+        self.eval_in_place(&mut ys);
+        ys
+    }
+
+    /// Like [`Poly::eval_many`], but overwrites each value of `x` with the
+    /// value of the polynomial, without allocating.
+    ///
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use num::Complex;
+    ///
+    /// let p: Poly<f64> = poly![1.0, 0.0, 1.0];
+    /// let mut xs = [Complex::new(0.0, 0.0), Complex::new(2.0, 0.0)];
+    /// p.eval_in_place(&mut xs);
+    /// assert_eq!(xs, [Complex::new(1.0, 0.0), Complex::new(5.0, 0.0)]);
+    /// ```
+    pub fn eval_in_place(&self, xs: &mut [Complex<T>]) {
+        let mut chunks = xs.chunks_exact_mut(EVAL_LANES);
+        for chunk in &mut chunks {
+            let x: [Complex<T>; EVAL_LANES] = std::array::from_fn(|l| chunk[l].clone());
+            let mut acc: [Complex<T>; EVAL_LANES] = std::array::from_fn(|_| Complex::zero());
+            for c in self.0.iter().rev() {
+                for (acc, x) in acc.iter_mut().zip(&x) {
+                    *acc = acc.clone() * x.clone() + c;
+                }
+            }
+            chunk.clone_from_slice(&acc);
+        }
+        for x in chunks.into_remainder() {
+            *x = self.eval_point(x.clone());
+        }
+    }
+
+    /// Like [`Poly::eval_many`], but for real values of `x`, with real
+    /// multiplications.
+    ///
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use num::Complex;
+    ///
+    /// let p: Poly<f64> = poly![1.0, 0.0, 1.0];
+    /// let ys = p.eval_many_real(&[0.0, 1.0, 2.0]);
+    /// assert_eq!(ys, [1.0, 2.0, 5.0].map(|y| Complex::new(y, 0.0)));
+    /// ```
+    #[must_use]
+    pub fn eval_many_real(&self, xs: &[T]) -> Vec<Complex<T>> {
+        let mut ys = Vec::with_capacity(xs.len());
+        let mut chunks = xs.chunks_exact(EVAL_LANES);
+        for chunk in &mut chunks {
+            let mut acc: [Complex<T>; EVAL_LANES] = std::array::from_fn(|_| Complex::zero());
+            for c in self.0.iter().rev() {
+                for (acc, x) in acc.iter_mut().zip(chunk) {
+                    *acc = acc.scale(x.clone()) + c;
+                }
+            }
+            ys.extend(acc);
+        }
+        ys.extend(
+            chunks
+                .remainder()
+                .iter()
+                .map(|x| self.eval_point_real(x.clone())),
+        );
+        ys
+    }
+
+    /// Evaluate the polynomial and its derivatives at a single value of `x`,
+    /// without allocating. The `j`-th derivative is written to `out[j]`, so
+    /// the length of `out` is the number of derivatives plus one.
+    ///
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use num::Complex;
+    ///
+    /// let p: Poly<f64> = poly![1.0, 2.0, 3.0];
+    /// let mut out = [Complex::new(0.0, 0.0); 4];
+    /// p.eval_derivatives(Complex::new(1.0, 0.0), &mut out);
+    /// assert_eq!(out.map(|c| c.re), [6.0, 8.0, 6.0, 0.0]);
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn eval_derivatives(&self, x: Complex<T>, out: &mut [Complex<T>]) {
+        out.fill(Complex::zero());
+        for c in self.0.iter().rev() {
+            for j in (1..out.len()).rev() {
+                out[j] = out[j].clone() * x.clone() + out[j - 1].clone();
+            }
+            if let Some(value) = out.first_mut() {
+                *value = value.clone() * x.clone() + c;
+            }
+        }
+
+        // out[j] is now the j-th Taylor coefficient, p^(j)(x) / j!
+        let mut j = T::zero();
+        let mut factorial = T::one();
+        for d in out.iter_mut().skip(1) {
+            j += T::one();
+            factorial *= j.clone();
+            *d = d.scale(factorial.clone());
+        }
     }
 }
 
@@ -327,6 +559,38 @@ impl<T: Scalar + Float> Poly<T> {
 
         self.as_slice().iter().all(|c| c.norm() <= *tolerance)
     }
+
+    /// Evaluate the polynomial at a single value of `x` with the compensated
+    /// Horner scheme, which uses error-free transformations to be as accurate
+    /// as Horner's method in twice the working precision. Also returns a
+    /// running bound on the absolute error of the value.
+    ///
+    /// If the modulus of the value is below the bound, the value cannot be
+    /// distinguished from zero, i.e. `x` is a root to working precision.
+    /// Newton's and Halley's methods stop there, with `eps |x| |p'(x)|` added
+    /// for the rounding of `x`, even if the value is above their `epsilon`.
+    ///
+    /// Ref: S. Graillat, V. Ménissier-Morain "Accurate summation, dot product
+    /// and polynomial evaluation in complex floating point arithmetic"
+    /// [DOI](https://doi.org/10.1016/j.ic.2012.03.001)
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use num::Complex;
+    ///
+    /// // (x - 1)^5, naive Horner gets no correct digits at this point
+    /// let p: Poly<f64> = poly![-1.0, 5.0, -10.0, 10.0, -5.0, 1.0];
+    /// let x = Complex::new(1.0001, 0.0);
+    /// let exact = (x - 1.0).powi(5);
+    /// let (value, bound) = p.eval_compensated(x);
+    /// assert!((value - exact).norm() <= bound);
+    /// assert!(bound < 1E-9 * exact.norm());
+    /// ```
+    #[must_use]
+    pub fn eval_compensated(&self, x: Complex<T>) -> (Complex<T>, T) {
+        horner_compensated_with_bound(self.as_slice(), x)
+    }
 }
 
 impl<T: Scalar + Display + PartialOrd> Display for Poly<T> {
@@ -346,6 +610,10 @@ impl<T: Scalar + Display + PartialOrd> Display for Poly<T> {
 
 #[cfg(test)]
 mod test {
+    use num::Zero;
+
+    use crate::Poly64;
+
     #[test]
     fn translate() {
         let p = poly![1.0, 2.0, 3.0];
@@ -363,4 +631,64 @@ mod test {
             "2 + 4.5*x^1 + (5+i)*x^2 + (6+i1.5)*x^3 + (7+i2)*x^4".to_string()
         );
     }
+
+    #[test]
+    fn eval_matrix() {
+        let a = na::DMatrix::from_row_slice(
+            3,
+            3,
+            &[
+                complex!(0.5),
+                complex!(-1.0, 0.5),
+                complex!(0.0),
+                complex!(0.25),
+                complex!(0.1),
+                complex!(1.0),
+                complex!(-0.5),
+                complex!(0.0, 1.0),
+                complex!(0.3),
+            ],
+        );
+        for degree in [0, 1, 2, 5, 9, 10, 17] {
+            let coeffs: Vec<f64> = (0..=degree).map(|k| 1.0 / (k as f64 + 1.0)).collect();
+            let p = Poly64::from_real_slice(&coeffs);
+            let mut expected = na::DMatrix::zeros(3, 3);
+            let mut power = na::DMatrix::identity(3, 3);
+            for &c in &coeffs {
+                expected += &power * complex!(c);
+                power *= &a;
+            }
+            let result = p.eval_matrix(&a).unwrap();
+            assert!((result - expected).norm() < 1E-12, "{degree}");
+        }
+        assert!(Poly64::zero().eval_matrix(&a).unwrap().iter().all(Zero::is_zero));
+        assert!(Poly64::zero().eval(&a).iter().all(Zero::is_zero));
+    }
+
+    #[test]
+    fn eval_many() {
+        let p = poly![(2.0, 1.0), (4.5, 0.0), (5.0, 1.0), (6.0, 1.5), (7.0, 2.0)];
+        for n in [0, 1, 7, 8, 9, 100] {
+            let xs: Vec<f64> = (0..n).map(|k| f64::from(k) * 0.1 - 2.0).collect();
+            let zs: Vec<_> = xs.iter().map(|&x| complex!(x, 0.5 - x)).collect();
+            let expected: Vec<_> = zs.iter().map(|&z| p.eval_point(z)).collect();
+            assert_eq!(p.eval_many(&zs), expected);
+            let expected: Vec<_> = xs.iter().map(|&x| p.eval_point_real(x)).collect();
+            assert_eq!(p.eval_many_real(&xs), expected);
+        }
+    }
+
+    #[test]
+    fn eval_derivatives() {
+        let p = poly![(2.0, 1.0), (4.5, 0.0), (5.0, 1.0), (6.0, 1.5), (7.0, 2.0)];
+        let x = complex!(0.3, -1.2);
+        let mut out = [complex!(0.0); 6];
+        p.eval_derivatives(x, &mut out);
+        let mut q = p.clone();
+        for d in out {
+            assert!((d - q.eval_point(x)).norm() < 1E-12);
+            q = q.diff();
+        }
+        assert_eq!(p.eval_point_real(0.5), p.eval_point(complex!(0.5)));
+    }
 }
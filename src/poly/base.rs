@@ -92,7 +92,6 @@ impl<T: Scalar> Poly<T> {
         // fill the rightmost column with the coefficients of the associated
         // monic polynomial
         let mut monic = self.clone();
-        println!("{monic:?}");
         monic.make_monic();
         for i in 0..n {
             mat.column_mut(n - 1)[i] = c_neg(monic[i].clone());
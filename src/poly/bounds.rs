@@ -0,0 +1,244 @@
+//! Bounds on the moduli of the roots, see [`Poly::root_annulus`].
+
+use na::RealField;
+use num::{Complex, Float, Zero};
+
+use crate::{Poly, Scalar};
+
+/// Classical bounds on the moduli of the roots, for [`Poly::root_annulus`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RootBounds {
+    /// `1 + max |a_k / a_n|`
+    Cauchy,
+
+    /// `2 max |a_{n-k} / a_n|^(1/k)`, with the last term halved, which is
+    /// within a factor of two of the largest root
+    Fujiwara,
+
+    /// `max(1, sum |a_k / a_n|)`
+    Lagrange,
+
+    /// `2 max |a_{k-1} / a_k|`, with the last term halved, infinite if any
+    /// coefficient but the constant one is zero
+    Kojima,
+
+    /// The unique positive root of Cauchy's polynomial
+    /// `|a_n| z^n - |a_{n-1}| z^(n-1) - ... - |a_0|`, which is the smallest
+    /// bound that only depends on the moduli of the coefficients. It is
+    /// computed with Newton's method and rounded outwards.
+    Tight,
+}
+
+/// All roots `z` satisfy `inner <= |z| <= outer`, see [`Poly::root_annulus`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RootAnnulus<T> {
+    pub inner: T,
+    pub outer: T,
+}
+
+impl<T: Scalar + Float> RootAnnulus<T> {
+    #[must_use]
+    pub fn contains(&self, z: Complex<T>) -> bool {
+        let r = z.norm();
+        self.inner <= r && r <= self.outer
+    }
+}
+
+impl<T: Scalar + RealField + Float> Poly<T> {
+    /// An annulus centered at the origin that contains all roots.
+    ///
+    /// The outer radius is an upper bound on the moduli of the roots, and
+    /// the inner radius is the reciprocal of the same bound for the reversed
+    /// polynomial, whose roots are the reciprocals. The inner radius is zero
+    /// if zero is a root. For constant polynomials both are zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly, RootBounds};
+    /// use num::Complex;
+    ///
+    /// // (x - 1)(x - 2)(x - 4)
+    /// let p: Poly<f64> = poly![-8.0, 14.0, -7.0, 1.0];
+    /// let cauchy = p.root_annulus(RootBounds::Cauchy);
+    /// assert_eq!(cauchy.outer, 15.0);
+    /// let tight = p.root_annulus(RootBounds::Tight);
+    /// assert!(tight.outer < cauchy.outer && tight.inner > cauchy.inner);
+    /// for r in [1.0, 2.0, 4.0] {
+    ///     assert!(tight.contains(Complex::new(r, 0.0)));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn root_annulus(&self, bound: RootBounds) -> RootAnnulus<T> {
+        debug_assert!(self.is_normalized());
+
+        let moduli: Vec<T> = self.iter().map(|&c| c.norm()).collect();
+        if moduli.len() < 2 {
+            return RootAnnulus {
+                inner: T::zero(),
+                outer: T::zero(),
+            };
+        }
+        let outer = upper_bound(bound, &moduli);
+        let inner = if moduli[0].is_zero() {
+            T::zero()
+        } else {
+            let reversed: Vec<T> = moduli.iter().rev().copied().collect();
+            Float::recip(upper_bound(bound, &reversed))
+        };
+        RootAnnulus { inner, outer }
+    }
+}
+
+/// Upper bound on the moduli of the roots of a polynomial whose coefficients
+/// have the moduli `moduli`, lowest degree first, with a non-zero leading
+/// coefficient
+fn upper_bound<T: Scalar + RealField + Float>(bound: RootBounds, moduli: &[T]) -> T {
+    let n = moduli.len() - 1;
+    let lead = moduli[n];
+    let two = T::one() + T::one();
+    match bound {
+        RootBounds::Cauchy => {
+            T::one()
+                + moduli[..n]
+                    .iter()
+                    .fold(T::zero(), |acc, &m| Float::max(acc, m / lead))
+        }
+        RootBounds::Fujiwara => {
+            two * (0..n).fold(T::zero(), |acc, k| {
+                let mut ratio = moduli[k] / lead;
+                if k == 0 {
+                    ratio /= two;
+                }
+                let power = Float::recip(T::from_usize(n - k).expect("infallible"));
+                Float::max(acc, Float::powf(ratio, power))
+            })
+        }
+        RootBounds::Lagrange => Float::max(
+            T::one(),
+            moduli[..n].iter().fold(T::zero(), |acc, &m| acc + m / lead),
+        ),
+        RootBounds::Kojima => {
+            if moduli[1..].iter().any(Zero::is_zero) {
+                return T::infinity();
+            }
+            (1..=n).fold(T::zero(), |acc, k| {
+                let ratio = moduli[k - 1] / moduli[k];
+                Float::max(acc, if k == 1 { ratio } else { two * ratio })
+            })
+        }
+        RootBounds::Tight => {
+            // with `w = 1 / z`, the positive root of Cauchy's polynomial is
+            // the reciprocal of the positive root of
+            // `|a_0| w^n + ... + |a_{n-1}| w - |a_n|`
+            let reversed: Vec<T> = moduli.iter().rev().copied().collect();
+            Float::recip(cauchy_root(&reversed))
+        }
+    }
+}
+
+/// The unique positive root of `c_n x^n + ... + c_1 x - c_0` with `c_k >= 0`
+/// and `c_0 > 0`, rounded down, or infinity if `c_k = 0` for all `k > 0`.
+pub(super) fn cauchy_root<T: Scalar + RealField + Float>(c: &[T]) -> T {
+    let eval = |x: T| {
+        c.iter()
+            .skip(1)
+            .rev()
+            .fold(T::zero(), |acc, &ck| (acc + ck) * x)
+            - c[0]
+    };
+    let eval_diff = |x: T| {
+        c.iter()
+            .enumerate()
+            .skip(1)
+            .rev()
+            .fold(T::zero(), |acc, (k, &ck)| {
+                acc * x + ck * T::from_usize(k).expect("infallible")
+            })
+    };
+
+    // every term on its own has a positive root which is an upper bound, the
+    // function is increasing and convex so Newton's method approaches the
+    // root monotonically from the right
+    let mut x = c
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, ck)| !ck.is_zero())
+        .map(|(k, &ck)| {
+            Float::powf(
+                c[0] / ck,
+                Float::recip(T::from_usize(k).expect("infallible")),
+            )
+        })
+        .fold(T::infinity(), Float::min);
+    if !Float::is_finite(x) {
+        return x;
+    }
+    // the iterates decrease until rounding errors take over
+    loop {
+        let next = x - eval(x) / eval_diff(x);
+        if Float::is_nan(next) || next >= x {
+            break;
+        }
+        x = next;
+    }
+
+    // round down until the polynomial is not positive
+    let mut step = T::epsilon();
+    while eval(x) > T::zero() {
+        x *= T::one() - step;
+        step += step;
+    }
+    Float::max(x, T::zero())
+}
+
+#[cfg(test)]
+mod test {
+    use num::complex::Complex64;
+
+    use crate::{Poly, Poly64, RootBounds};
+
+    const ALL: [RootBounds; 5] = [
+        RootBounds::Cauchy,
+        RootBounds::Fujiwara,
+        RootBounds::Lagrange,
+        RootBounds::Kojima,
+        RootBounds::Tight,
+    ];
+
+    #[test]
+    fn bounds_contain_roots() {
+        let roots: Vec<_> = [-3.0, 0.5, 7.0, 1E-3]
+            .iter()
+            .map(|&r| Complex64::new(r, 0.0))
+            .chain([Complex64::new(0.2, 2.0), Complex64::new(0.2, -2.0)])
+            .collect();
+        let p = Poly::from_roots(&roots);
+        let tight = p.root_annulus(RootBounds::Tight);
+        for bound in ALL {
+            let annulus = p.root_annulus(bound);
+            assert!(roots.iter().all(|&z| annulus.contains(z)), "{bound:?}");
+            assert!(annulus.inner <= tight.inner && tight.outer <= annulus.outer);
+        }
+    }
+
+    #[test]
+    fn tight_bound_is_attained() {
+        // all the roots of z^n - 1 are on the unit circle, and the tight
+        // bound is the positive root of z^n - 1
+        let mut coeffs = vec![0.0; 11];
+        coeffs[0] = -1.0;
+        coeffs[10] = 1.0;
+        let p = Poly64::from_real_slice(&coeffs);
+        let tight = p.root_annulus(RootBounds::Tight);
+        assert!(tight.inner <= 1.0 && tight.inner > 1.0 - 1E-14);
+        assert!(tight.outer >= 1.0 && tight.outer < 1.0 + 1E-14);
+        // infinite Kojima bound because of the zero coefficients
+        assert!(p.root_annulus(RootBounds::Kojima).outer.is_infinite());
+        // zero root
+        let q = Poly::from_roots(&[Complex64::new(0.0, 0.0), Complex64::new(2.0, 0.0)]);
+        assert_eq!(q.root_annulus(RootBounds::Tight).inner, 0.0);
+        assert_eq!(q.root_annulus(RootBounds::Tight).outer, 2.0);
+    }
+}
@@ -0,0 +1,191 @@
+//! Sensitivity of the roots and of the values to relative perturbations of
+//! the coefficients in the monomial basis.
+
+use na::RealField;
+use num::{Complex, Float, Zero};
+
+use super::roots::horner_with_bound;
+use crate::{Poly, Scalar};
+
+/// Number of golden section steps when refining the minimum on the unit circle
+const GOLDEN_SECTION_STEPS: usize = 64;
+
+impl<T: Scalar + RealField + Float> Poly<T> {
+    /// The Wilkinson condition number of each root, i.e. the factor by which
+    /// a relative perturbation of the coefficients is amplified in the
+    /// relative error of the root, to first order:
+    ///
+    /// `(|a_0| + |a_1| |z| + ... + |a_n| |z|^n) / (|z| |p'(z)|)`
+    ///
+    /// For a zero root the absolute error is used instead, i.e. there is no
+    /// `|z|` in the denominator. Multiple roots have an infinite condition
+    /// number.
+    ///
+    /// Ref: J. H. Wilkinson "Rounding Errors in Algebraic Processes", 1963
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::Poly;
+    /// use num::complex::Complex64;
+    ///
+    /// let roots: Vec<_> = (1..=20).map(|k| Complex64::new(k as f64, 0.0)).collect();
+    /// let wilkinson = Poly::from_roots(&roots);
+    /// let cond = wilkinson.root_condition_numbers(&roots);
+    /// assert!(cond[0] < 1E4);
+    /// assert!(cond[14] > 1E13);
+    /// ```
+    #[must_use]
+    pub fn root_condition_numbers(&self, roots: &[Complex<T>]) -> Vec<T> {
+        debug_assert!(self.is_normalized());
+
+        roots
+            .iter()
+            .map(|&z| {
+                let (_, pdz, magnitude) = horner_with_bound(self.iter().rev(), z);
+                let scale = if z.is_zero() { T::one() } else { z.norm() };
+                magnitude / (scale * pdz.norm())
+            })
+            .collect()
+    }
+
+    /// The condition number of evaluating the polynomial on the unit circle,
+    /// i.e. the largest factor by which a relative perturbation of the
+    /// coefficients is amplified in the relative error of the value of the
+    /// frequency response:
+    ///
+    /// `max_{|z| = 1} (|a_0| + ... + |a_n|) / |p(z)|`
+    ///
+    /// The maximum is taken over the unit circle only, the condition number
+    /// elsewhere in the complex plane can be much larger. The minimum of
+    /// `|p|` on the circle is found by sampling and refining the smallest
+    /// sample, so it is an estimate. It is infinite or very large if a root
+    /// lies on the circle.
+    ///
+    /// A non-zero constant has condition number one, and the zero polynomial
+    /// an infinite one, since it has no correct relative digits.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    ///
+    /// // |z + 2| >= 1 on the unit circle
+    /// let p: Poly<f64> = poly![2.0, 1.0];
+    /// assert!((p.condition_number() - 3.0).abs() < 1E-12);
+    /// ```
+    #[must_use]
+    pub fn condition_number(&self) -> T {
+        debug_assert!(self.is_normalized());
+
+        if self.len_raw() < 2 {
+            return if self.iter().all(Zero::is_zero) {
+                T::infinity()
+            } else {
+                T::one()
+            };
+        }
+        let norm = self.iter().fold(T::zero(), |acc, c| acc + c.norm());
+        norm / self.min_on_unit_circle()
+    }
+}
+
+// private
+impl<T: Scalar + RealField + Float> Poly<T> {
+    /// Estimates the minimum of `|p(z)|` on the unit circle
+    fn min_on_unit_circle(&self) -> T {
+        let modulus = |theta: T| {
+            let z = Complex::from_polar(T::one(), theta);
+            self.iter()
+                .rev()
+                .fold(Complex::zero(), |acc, &c| acc * z + c)
+                .norm()
+        };
+
+        // sample densely enough to resolve every local minimum of a
+        // polynomial of this degree, then refine the smallest one
+        let samples = 16 * self.len_raw();
+        let step = T::two_pi() / T::from_usize(samples).expect("infallible");
+        let (best, _) = (0..samples)
+            .map(|i| (i, modulus(step * T::from_usize(i).expect("infallible"))))
+            .fold((0, T::infinity()), |(best, min), (i, m)| {
+                if m < min {
+                    (i, m)
+                } else {
+                    (best, min)
+                }
+            });
+
+        let ratio = (Float::sqrt(T::from_u8(5).expect("infallible")) - T::one())
+            / T::from_u8(2).expect("infallible");
+        let center = step * T::from_usize(best).expect("infallible");
+        let (mut a, mut b) = (center - step, center + step);
+        let (mut c, mut d) = (b - ratio * (b - a), a + ratio * (b - a));
+        let (mut fc, mut fd) = (modulus(c), modulus(d));
+        for _ in 0..GOLDEN_SECTION_STEPS {
+            if fc < fd {
+                b = d;
+                d = c;
+                fd = fc;
+                c = b - ratio * (b - a);
+                fc = modulus(c);
+            } else {
+                a = c;
+                c = d;
+                fc = fd;
+                d = a + ratio * (b - a);
+                fd = modulus(d);
+            }
+        }
+        Float::min(Float::min(fc, fd), modulus(center))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num::{complex::Complex64, Zero};
+
+    use crate::{Poly, Poly64};
+
+    #[test]
+    fn root_condition_numbers() {
+        // (x - 1)(x - 2), the terms add up to 6 at 1 and 12 at 2
+        let roots = [Complex64::new(1.0, 0.0), Complex64::new(2.0, 0.0)];
+        let p = Poly::from_roots(&roots);
+        assert_eq!(p.root_condition_numbers(&roots), vec![6.0, 6.0]);
+
+        // complex coefficients, x (x - i)^2 with a zero and a double root
+        let q = Poly::from_roots(&[
+            Complex64::new(0.0, 0.0),
+            Complex64::new(0.0, 1.0),
+            Complex64::new(0.0, 1.0),
+        ]);
+        let cond = q.root_condition_numbers(&[Complex64::new(0.0, 0.0), Complex64::new(0.0, 1.0)]);
+        assert_eq!(cond[0], 0.0);
+        assert!(cond[1].is_infinite());
+    }
+
+    #[test]
+    fn condition_number_of_constants() {
+        let zero: Poly64 = poly![];
+        assert_eq!(zero.condition_number(), f64::INFINITY);
+        assert_eq!(poly![0.0].condition_number(), f64::INFINITY);
+        assert_eq!(poly![-3.0].condition_number(), 1.0);
+    }
+
+    #[test]
+    fn condition_number_on_unit_circle() {
+        // |z^4| = 1 on the circle
+        let p = Poly64::from_real_slice(&[0.0, 0.0, 0.0, 0.0, 1.0]);
+        assert!((p.condition_number() - 1.0).abs() < 1E-14);
+
+        // a pair of roots close to the circle, the minimum is at the angle
+        // of the roots and is about 0.01 * 1.99 * sin(1)
+        let z = Complex64::from_polar(0.99, 1.0);
+        let q = Poly::from_roots(&[z, z.conj()]);
+        let expected = q.iter().map(|c| c.norm()).sum::<f64>() / (0.01 * (z - z.conj()).norm());
+        let cond = q.condition_number();
+        assert!(
+            cond > 0.9 * expected && cond < 1.1 * expected,
+            "{cond} {expected}"
+        );
+    }
+}
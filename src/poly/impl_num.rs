@@ -54,6 +54,11 @@ impl<T: ScalarOps> Poly<T> {
             let k = num_k - den_k;
             let new_term = Poly::term(c, k as u32);
             this = this.clone() - new_term.clone() * other;
+            // the leading term cancels exactly in theory, but rounding can
+            // leave a residue that would never go away
+            if this.degree_raw() == num_k {
+                this = Self::from_complex_slice(&this.as_slice()[..this.len_raw() - 1]).normalize();
+            }
             res = res + new_term;
         }
         let rem = this;
@@ -219,21 +224,18 @@ impl<T: Scalar> Sub<Self> for Poly<T> {
         debug_assert!(self.is_normalized());
         debug_assert!(rhs.is_normalized());
 
-        let (mut longest, shortest) = if self.len_raw() >= rhs.len_raw() {
-            (self.0, rhs.0)
-        } else {
-            (rhs.0, self.0)
-        };
-        longest
-            .as_mut_slice()
-            .iter_mut()
-            .zip_longest(shortest.iter())
-            .for_each(|p| {
-                if let itertools::EitherOrBoth::Both(l, r) = p {
-                    *l = l.clone() - r;
-                }
-            });
-        Self(longest).normalize()
+        // unlike addition, the operands can't be swapped
+        let coeffs = self
+            .0
+            .iter()
+            .zip_longest(rhs.0.iter())
+            .map(|p| match p {
+                itertools::EitherOrBoth::Both(l, r) => l.clone() - r.clone(),
+                itertools::EitherOrBoth::Left(l) => l.clone(),
+                itertools::EitherOrBoth::Right(r) => Complex::<T>::zero() - r.clone(),
+            })
+            .collect();
+        Self::from_complex_vec(coeffs).normalize()
     }
 }
 
@@ -413,4 +415,10 @@ mod test {
         assert_eq!(q, poly![3.0, 1.0, 1.0]);
         assert_eq!(r, poly![5.0]);
     }
+
+    #[test]
+    fn sub_longer_rhs() {
+        assert_eq!(poly![1.0, 2.0] - poly![1.0, 1.0, 3.0], poly![0.0, 1.0, -3.0]);
+        assert_eq!(-poly![1.0, -2.0], poly![-1.0, 2.0]);
+    }
 }
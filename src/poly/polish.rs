@@ -0,0 +1,204 @@
+//! Refinement of approximate roots against the original polynomial.
+
+use na::RealField;
+use num::{Complex, Float};
+
+use super::roots::{horner_derivatives, laguerre_correction};
+use crate::{__util::compensated::horner_compensated, Poly, Scalar};
+
+/// Methods for [`Poly::polish_roots`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PolishAlgorithms {
+    Newton,
+
+    /// Converges cubically near simple roots, and it is less likely to jump
+    /// to a different root than Newton when the roots are close together
+    Laguerre,
+}
+
+/// A root refined by [`Poly::polish_roots`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolishedRoot<T> {
+    pub root: Complex<T>,
+
+    /// The modulus of the polynomial at the root before polishing
+    pub initial_residual: T,
+
+    /// The modulus of the polynomial at the root after polishing
+    pub residual: T,
+
+    /// The number of steps that were taken, every step reduces the residual
+    pub iterations: usize,
+}
+
+impl<T: Float> PolishedRoot<T> {
+    /// The factor by which the residual was reduced, infinite if it became
+    /// zero
+    pub fn improvement(&self) -> T {
+        self.initial_residual / self.residual
+    }
+}
+
+impl<T: Scalar + RealField + Float> Poly<T> {
+    /// Refine approximations of the roots, for example the ones returned by
+    /// [`Poly::try_roots`], with up to `max_iter` steps of Newton's or
+    /// Laguerre's method each.
+    ///
+    /// Every root is refined independently against this polynomial, so the
+    /// errors introduced by deflation or by the eigenvalue algorithms are not
+    /// carried over. The polynomial is evaluated in compensated arithmetic,
+    /// which is about as accurate as twice the working precision, and a step
+    /// is only taken if it reduces the residual.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{PolishAlgorithms, Poly};
+    /// use num::Complex;
+    ///
+    /// let p = Poly::from_roots(&[Complex::new(1.0, 0.0), Complex::new(2.0, 0.0)]);
+    /// let rough = [Complex::new(1.001, 0.0), Complex::new(1.999, 0.0)];
+    /// let polished = p.polish_roots(&rough, PolishAlgorithms::Newton, 10);
+    /// assert!((polished[0].root - 1.0).norm() < 1E-15);
+    /// assert!(polished[1].improvement() > 1E10);
+    /// ```
+    #[must_use]
+    pub fn polish_roots(
+        &self,
+        roots: &[Complex<T>],
+        algorithm: PolishAlgorithms,
+        max_iter: usize,
+    ) -> Vec<PolishedRoot<T>> {
+        debug_assert!(self.is_normalized());
+
+        roots
+            .iter()
+            .map(|&root| self.polish_root(root, algorithm, max_iter))
+            .collect()
+    }
+}
+
+// private
+impl<T: Scalar + RealField + Float> Poly<T> {
+    fn polish_root(
+        &self,
+        mut root: Complex<T>,
+        algorithm: PolishAlgorithms,
+        max_iter: usize,
+    ) -> PolishedRoot<T> {
+        let coeffs = self.as_slice();
+        let degree = T::from_usize(self.len_raw().saturating_sub(1)).expect("infallible");
+        let mut px = horner_compensated(coeffs, root);
+        let initial_residual = px.norm();
+        let mut residual = initial_residual;
+        let mut iterations = 0;
+        while iterations < max_iter && !residual.is_zero() {
+            let (_, slope, curvature) = horner_derivatives(coeffs, root);
+            let step = match algorithm {
+                PolishAlgorithms::Newton => px / slope,
+                PolishAlgorithms::Laguerre => laguerre_correction(px, slope, curvature, degree),
+            };
+            let next = root - step;
+            let next_px = horner_compensated(coeffs, next);
+            let next_residual = next_px.norm();
+            if Float::is_nan(next_residual) || next_residual >= residual {
+                break;
+            }
+            (root, px, residual) = (next, next_px, next_residual);
+            iterations += 1;
+        }
+        PolishedRoot {
+            root,
+            initial_residual,
+            residual,
+            iterations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num::complex::Complex64;
+
+    use crate::{AllRootsAlgorithms, PolishAlgorithms, Poly, Poly64, RootFinder};
+
+    #[test]
+    fn polish_wilkinson() {
+        let exact: Vec<_> = (1..=15)
+            .map(|r| Complex64::new(f64::from(r), 0.0))
+            .collect();
+        let p = Poly::from_roots(&exact);
+        let mut rough = p
+            .try_roots(
+                1E-14,
+                1000,
+                10,
+                None,
+                Some(AllRootsAlgorithms::MultishiftQR),
+                None,
+            )
+            .unwrap();
+        rough.sort_by(|a, b| a.re.total_cmp(&b.re));
+        for algorithm in [PolishAlgorithms::Newton, PolishAlgorithms::Laguerre] {
+            let polished = p.polish_roots(&rough, algorithm, 5);
+            for ((r, q), z) in rough.iter().zip(&polished).zip(&exact) {
+                assert!(q.residual <= q.initial_residual);
+                assert!((q.root - z).norm() <= (r - z).norm());
+            }
+        }
+    }
+
+    #[test]
+    fn laguerre_from_far_away() {
+        // Laguerre converges to a real root from anywhere on the real line
+        // if all roots are real
+        let p = Poly::from_roots(&[-1.0, 2.0, 3.0].map(|r| Complex64::new(r, 0.0)));
+        let polished = p.polish_roots(
+            &[Complex64::new(100.0, 0.0)],
+            PolishAlgorithms::Laguerre,
+            50,
+        );
+        assert!((polished[0].root - 3.0).norm() < 1E-14);
+        assert_eq!(polished[0].residual, 0.0);
+    }
+
+    #[test]
+    fn root_finder_reports_improvement() {
+        let p = Poly::from_roots(
+            &(1..=12)
+                .map(|r| Complex64::new(f64::from(r), 0.0))
+                .collect::<Vec<_>>(),
+        );
+        let report = RootFinder::new()
+            .algorithm(AllRootsAlgorithms::MultishiftQR)
+            .polish_iter(3)
+            .polish_algorithm(PolishAlgorithms::Laguerre)
+            .run(&p)
+            .unwrap();
+        assert!(report
+            .roots
+            .iter()
+            .all(|r| r.residual <= r.unpolished_residual));
+        assert!(report
+            .roots
+            .iter()
+            .any(|r| r.residual < r.unpolished_residual));
+    }
+
+    #[test]
+    fn polish_bessel() {
+        let p = Poly64::bessel(85).unwrap();
+        let report = RootFinder::new()
+            .algorithm(AllRootsAlgorithms::MultishiftQR)
+            .polish_iter(5)
+            .run(&p)
+            .unwrap();
+        let before = report
+            .roots
+            .iter()
+            .map(|r| r.unpolished_residual)
+            .fold(0.0, f64::max);
+        let after = report.roots.iter().map(|r| r.residual).fold(0.0, f64::max);
+        assert!(after < before / 10.0);
+    }
+}
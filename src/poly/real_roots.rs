@@ -0,0 +1,758 @@
+//! Counting, isolating and finding the roots of polynomials with real
+//! coefficients.
+//!
+//! The imaginary parts of the coefficients are ignored throughout.
+
+use na::{DMatrix, RealField};
+use num::{Complex, Float, One, Zero};
+
+use crate::{
+    __util::real_qr::{balance_real, eigen_real_double_shift},
+    Error, Poly, Scalar,
+};
+
+/// Maximum number of bisections of the isolating intervals, enough to reach
+/// the resolution of `f64` on the whole range of exponents
+const MAX_BISECTIONS: usize = 2100;
+
+/// Maximum number of sweeps when balancing the companion matrix
+const MAX_ITER_BALANCE: usize = 100;
+
+/// Where to split an interval relative to its length, in order of preference
+pub(super) const SPLIT_POINTS: [f64; 5] = [0.5, 0.4375, 0.5625, 0.375, 0.625];
+
+/// The roots of a polynomial with real coefficients, found by
+/// [`Poly::try_conjugate_roots`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConjugateRoots<T> {
+    pub real: Vec<T>,
+
+    /// One root of each complex conjugate pair, the one with positive
+    /// imaginary part
+    pub pairs: Vec<Complex<T>>,
+}
+
+impl<T: Scalar + Float> ConjugateRoots<T> {
+    /// All roots as complex numbers, every pair `z` is followed by its
+    /// conjugate, then come the real roots
+    #[must_use]
+    pub fn to_complex(&self) -> Vec<Complex<T>> {
+        self.pairs
+            .iter()
+            .flat_map(|z| [*z, z.conj()])
+            .chain(self.real.iter().map(|&r| Complex::from(r)))
+            .collect()
+    }
+}
+
+impl<T: Scalar + Float + RealField> Poly<T> {
+    /// The Sturm sequence `p_0 = p`, `p_1 = p'`, `p_{k+1} = -rem(p_{k-1}, p_k)`,
+    /// ending with a constant or, if `p` has multiple roots, with (a multiple
+    /// of) `gcd(p, p')`.
+    ///
+    /// Remainders are computed in floating point, so coefficients that are
+    /// negligible compared to the dividend are discarded, as they are most
+    /// likely the result of cancellation.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    ///
+    /// let p = poly![-1.0, 0.0, 1.0];
+    /// assert_eq!(p.sturm_sequence(), vec![poly![-1.0, 0.0, 1.0], poly![0.0, 2.0], poly![1.0]]);
+    /// ```
+    #[must_use]
+    pub fn sturm_sequence(&self) -> Vec<Self> {
+        debug_assert!(self.is_normalized());
+
+        let mut seq = vec![self.real_part()];
+        if seq[0].len_raw() < 2 {
+            return seq;
+        }
+        let mut next = seq[0].clone().diff();
+        loop {
+            seq.push(next);
+            let (prev, last) = (&seq[seq.len() - 2], &seq[seq.len() - 1]);
+            if last.len_raw() < 2 {
+                return seq;
+            }
+            // the divisor is never zero, as the sequence stops before that
+            let Some((quot, rem)) = prev.clone().div_rem(last) else {
+                return seq;
+            };
+            next = -rem.chop(prev.division_tolerance(&quot));
+            if next.is_zero() {
+                return seq;
+            }
+        }
+    }
+
+    /// The number of distinct real roots in the interval `(a, b]`, using
+    /// Sturm's theorem.
+    ///
+    /// The Sturm sequence of the square-free part is used, computed with an
+    /// approximate GCD, since a sequence that is supposed to end with
+    /// `gcd(p, p')` is easily spoiled by rounding errors.
+    ///
+    /// The bounds may be infinite.
+    ///
+    /// # Panics
+    /// If `a > b`
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    ///
+    /// // (x + 1) x (x - 1)^2
+    /// let p = poly![0.0, 1.0, -1.0, -1.0, 1.0];
+    /// assert_eq!(p.count_real_roots_in(-2.0, 2.0), 3);
+    /// assert_eq!(p.count_real_roots_in(-1.0, 0.5), 1);
+    /// assert_eq!(p.count_real_roots_in(f64::NEG_INFINITY, f64::INFINITY), 3);
+    /// ```
+    #[must_use]
+    pub fn count_real_roots_in(&self, a: T, b: T) -> usize {
+        assert!(a <= b, "the interval must not be empty");
+        debug_assert!(self.is_normalized());
+
+        let seq = self.square_free_part().sturm_sequence();
+        sign_changes(&seq, a).saturating_sub(sign_changes(&seq, b))
+    }
+
+    /// Disjoint open intervals `(lo, hi)`, each containing exactly one
+    /// distinct real root in `[a, b]`, sorted in ascending order.
+    ///
+    /// Uses bisection with Descartes' rule of signs on the square-free part of
+    /// the polynomial. If a root is found exactly, its interval is degenerate,
+    /// i.e. both bounds are equal. Distinct roots that are closer than the
+    /// floating point resolution cannot be separated and share an interval.
+    ///
+    /// Ref: G. E. Collins, A. G. Akritas "Polynomial real root isolation using
+    /// Descarte's rule of signs" [DOI](https://doi.org/10.1145/800205.806346)
+    ///
+    /// # Panics
+    /// If `a > b` or either bound is not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    ///
+    /// // (x + 1) x (x - 1)^2
+    /// let p = poly![0.0, 1.0, -1.0, -1.0, 1.0];
+    /// let intervals = p.isolate_real_roots(-2.0, 2.0);
+    /// assert_eq!(intervals.len(), 3);
+    /// assert!(intervals[0].0 < -1.0 && -1.0 < intervals[0].1);
+    /// assert_eq!(intervals[1], (0.0, 0.0));
+    /// assert!(intervals[2].0 < 1.0 && 1.0 < intervals[2].1);
+    /// ```
+    #[must_use]
+    pub fn isolate_real_roots(&self, a: T, b: T) -> Vec<(T, T)> {
+        assert!(a <= b, "the interval must not be empty");
+        assert!(
+            Float::is_finite(a) && Float::is_finite(b),
+            "the interval must be bounded"
+        );
+        debug_assert!(self.is_normalized());
+
+        // exact zero roots are split off, so that the approximate square-free
+        // part does not have a root close to, but not exactly at zero
+        let num_zeros = self.iter().take_while(|c| c.re.is_zero()).count();
+        let square_free = self.shift_down(num_zeros).square_free_part();
+        let eval = |x: T| square_free.eval_point_real(x).re;
+        let tol = T::from_usize(8 * square_free.len_raw()).expect("infallible") * T::epsilon();
+        let is_tiny = |x: T| {
+            let bound = square_free
+                .iter()
+                .rev()
+                .fold(T::zero(), |acc, c| acc * Float::abs(x) + c.norm());
+            Float::abs(eval(x)) <= tol * bound
+        };
+
+        let mut intervals = vec![];
+        let mut stack = vec![];
+        if num_zeros > 0 && a <= T::zero() && T::zero() <= b {
+            intervals.push((T::zero(), T::zero()));
+            stack.extend([(a, T::zero(), 0), (T::zero(), b, 0)]);
+        } else {
+            stack.push((a, b, 0));
+        }
+        if square_free.len_raw() < 2 {
+            stack.clear();
+        }
+        for x in [a, b] {
+            if eval(x).is_zero() {
+                intervals.push((x, x));
+            }
+        }
+
+        while let Some((lo, hi, depth)) = stack.pop() {
+            if lo >= hi {
+                continue;
+            }
+            // the interval is not split too close to a root, where the sign of
+            // the polynomial is unreliable, so roots don't get lost in between
+            let mid = SPLIT_POINTS
+                .iter()
+                .map(|&t| lo + (hi - lo) * T::from_f64(t).expect("infallible"))
+                .find(|&x| !is_tiny(x))
+                .unwrap_or_else(|| lo + (hi - lo) / T::from_u8(2).expect("infallible"));
+            let unresolved = mid <= lo || mid >= hi || depth >= MAX_BISECTIONS;
+            match square_free.descartes_bound(lo, hi) {
+                0 => {}
+                // a sign change confirms the single root, in case the
+                // transformed coefficients are spoiled by rounding errors
+                1 if eval(lo) * eval(hi) < T::zero() => intervals.push((lo, hi)),
+                _ if unresolved => intervals.push((lo, hi)),
+                _ => {
+                    if eval(mid).is_zero() {
+                        intervals.push((mid, mid));
+                    }
+                    stack.push((mid, hi, depth + 1));
+                    stack.push((lo, mid, depth + 1));
+                }
+            }
+        }
+        intervals.sort_by(|x, y| x.partial_cmp(y).expect("bounds are finite"));
+        intervals.dedup();
+        intervals
+    }
+
+    /// Find a real root in the interval `[a, b]`, which must be a bracket,
+    /// i.e. the polynomial must have opposite signs at its ends, to within
+    /// `tol`.
+    ///
+    /// Uses the ITP method, which converges superlinearly for simple roots,
+    /// but never takes more iterations than bisection does, plus one. Unlike
+    /// [`Poly::try_n_roots`], it never leaves the interval. The polynomial is
+    /// evaluated in real arithmetic, ignoring the imaginary parts of the
+    /// coefficients.
+    ///
+    /// Ref: I. F. D. Oliveira, R. H. C. Takahashi "An Enhancement of the
+    /// Bisection Method Average Performance Preserving Minmax Optimality"
+    /// [DOI](https://doi.org/10.1145/3423597)
+    ///
+    /// # Errors
+    /// If the polynomial does not change sign in the interval, if `a` or `b`
+    /// is not finite, or if `tol` is not positive and finite.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    ///
+    /// let p = poly![-2.0, 0.0, 1.0];
+    /// let root = p.real_root_in(0.0, 2.0, 1E-14).unwrap();
+    /// assert!((root - 2.0f64.sqrt()).abs() <= 1E-14);
+    /// assert!(p.real_root_in(-1.0, 1.0, 1E-14).is_err());
+    /// ```
+    pub fn real_root_in(&self, a: T, b: T, tol: T) -> Result<T, Error> {
+        debug_assert!(self.is_normalized());
+
+        if !Float::is_finite(a) || !Float::is_finite(b) {
+            return Err(Error::not_finite());
+        }
+        if !Float::is_finite(tol) || tol <= T::zero() {
+            return Err(Error::invalid_tolerance());
+        }
+        let (lo, hi) = (Float::min(a, b), Float::max(a, b));
+        let (f_lo, f_hi) = (self.eval_real(lo), self.eval_real(hi));
+        if f_lo.is_zero() {
+            return Ok(lo);
+        }
+        if f_hi.is_zero() {
+            return Ok(hi);
+        }
+        if f_lo.is_nan() || f_hi.is_nan() || Float::signum(f_lo) == Float::signum(f_hi) {
+            return Err(Error::no_sign_change());
+        }
+        Ok(self.itp(lo, hi, f_lo, f_hi, tol))
+    }
+
+    /// All roots, computed in real arithmetic with the double-shift Francis
+    /// QR algorithm on the balanced companion matrix, so that complex roots
+    /// come in exactly conjugate pairs and real roots have no imaginary part.
+    /// The imaginary parts of the coefficients are ignored.
+    ///
+    /// `max_iter` is the maximum number of iterations per root or pair of
+    /// roots.
+    ///
+    /// # Errors
+    /// If the algorithm does not converge within `max_iter` iterations, or
+    /// if balancing the companion matrix does not converge.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    ///
+    /// // (x - 2) (x^2 + 2x + 5)
+    /// let p: Poly<f64> = poly![-10.0, 1.0, 0.0, 1.0];
+    /// let roots = p.try_conjugate_roots(1E-14, 100).unwrap();
+    /// assert!((roots.real[0] - 2.0).abs() < 1E-14);
+    /// assert!((roots.pairs[0].re + 1.0).abs() < 1E-14);
+    /// assert!((roots.pairs[0].im - 2.0).abs() < 1E-14);
+    /// ```
+    pub fn try_conjugate_roots(
+        &self,
+        epsilon: T,
+        max_iter: usize,
+    ) -> Result<ConjugateRoots<T>, Error> {
+        debug_assert!(self.is_normalized());
+
+        let p = self.real_part();
+        let coeffs: Vec<T> = p.iter().map(|c| c.re).collect();
+        // zero roots are split off, they would only slow down convergence
+        let num_zeros = coeffs.iter().take_while(|c| c.is_zero()).count();
+        let coeffs = &coeffs[num_zeros.min(coeffs.len())..];
+        let n = coeffs.len().saturating_sub(1);
+
+        let (mut real, pairs) = if n == 0 {
+            (vec![], vec![])
+        } else {
+            let lead = coeffs[n];
+            let mut companion = DMatrix::zeros(n, n);
+            for j in 0..n {
+                companion[(0, j)] = -coeffs[n - 1 - j] / lead;
+            }
+            for i in 1..n {
+                companion[(i, i - 1)] = T::one();
+            }
+            balance_real(&mut companion, MAX_ITER_BALANCE).map_err(Error::map_inner)?;
+            eigen_real_double_shift(companion, epsilon, max_iter)?
+        };
+        real.extend(std::iter::repeat_n(T::zero(), num_zeros));
+        Ok(ConjugateRoots { real, pairs })
+    }
+
+    /// Monic real factors of degree two, and one of degree one if the degree
+    /// is odd, found one at a time with Bairstow's method, which iterates on
+    /// the coefficients of a quadratic factor in real arithmetic. The product
+    /// of the factors times the leading coefficient is the polynomial. The
+    /// imaginary parts of the coefficients are ignored.
+    ///
+    /// Every factor is divided out as soon as it is found, in the more stable
+    /// direction, see [`Poly::deflate_linear`]. Zero roots are split off as
+    /// factors `x` first.
+    ///
+    /// `max_iter` is the maximum number of iterations for each starting
+    /// point, up to one starting point per remaining root is tried for each
+    /// factor.
+    ///
+    /// Ref: L. Bairstow "Investigations relating to the stability of the
+    /// aeroplane" Reports and Memoranda 154, Advisory Committee for
+    /// Aeronautics, 1914
+    ///
+    /// # Errors
+    /// If Bairstow's method does not converge from any of the starting points.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    ///
+    /// // (x^2 + 2x + 5) (x - 3)
+    /// let p: Poly<f64> = poly![-15.0, -1.0, -1.0, 1.0];
+    /// let factors = p.quadratic_factors(1E-14, 100).unwrap();
+    /// assert_eq!(factors.len(), 2);
+    /// assert!((factors[0].clone() - poly![5.0, 2.0, 1.0]).almost_zero(&1E-12));
+    /// assert!((factors[1].clone() - poly![-3.0, 1.0]).almost_zero(&1E-12));
+    /// ```
+    pub fn quadratic_factors(&self, epsilon: T, max_iter: usize) -> Result<Vec<Self>, Error> {
+        debug_assert!(self.is_normalized());
+
+        let mut factors = vec![];
+        let mut p = self.real_part();
+        if p.len_raw() < 2 {
+            return Ok(factors);
+        }
+        p.make_monic();
+        while p.len_raw() > 1 && p.0[0].is_zero() {
+            factors.push(Self::from_real_slice(&[T::zero(), T::one()]));
+            p = p.shift_down(1);
+        }
+
+        while p.len_raw() > 3 {
+            let coeffs: Vec<T> = p.iter().map(|c| c.re).collect();
+            // the guesses on the innermost circle come first, and deflating
+            // the smallest roots first is the more stable order
+            let (r, s) = p
+                .initial_root_guesses()
+                .iter()
+                .find_map(|z| bairstow(&coeffs, z.re + z.re, -z.norm_sqr(), epsilon, max_iter))
+                .ok_or_else(Error::max_iter_inner)?;
+            // `x^2 - r x - s`
+            let divisor = [Complex::from(-s), Complex::from(-r), Complex::one()];
+            p = p.deflate_monic(&divisor).0.real_part();
+            p.make_monic();
+            factors.push(Self::from_complex_slice(&divisor));
+        }
+        if p.len_raw() > 1 {
+            factors.push(p);
+        }
+        Ok(factors)
+    }
+}
+
+// private
+impl<T: Scalar + Float + RealField> Poly<T> {
+    /// The ITP iteration on a valid bracket `[lo, hi]` with `f_lo = self(lo)`
+    /// and `f_hi = self(hi)` of opposite signs.
+    fn itp(&self, mut lo: T, mut hi: T, mut f_lo: T, mut f_hi: T, tol: T) -> T {
+        let two = T::from_u8(2).expect("infallible");
+        // the tolerance can't be finer than the spacing of floats
+        let tol = Float::max(
+            tol,
+            T::epsilon() * Float::max(Float::abs(lo), Float::abs(hi)),
+        );
+        let kappa1 = T::from_f64(0.2).expect("infallible") / (hi - lo);
+        let n_half = Float::ceil(Float::log2((hi - lo) / (two * tol)));
+        let n_max = Float::max(n_half, T::zero()) + T::one();
+
+        let mut iter = T::zero();
+        while hi - lo > two * tol {
+            let half_width = (hi - lo) / two;
+            let x_half = lo + half_width;
+            let radius = tol * Float::powf(two, n_max - iter) - half_width;
+            let delta = kappa1 * (hi - lo) * (hi - lo);
+
+            // truncated regula falsi, projected onto the minmax interval
+            let x_f = (f_hi * lo - f_lo * hi) / (f_hi - f_lo);
+            let sigma = Float::signum(x_half - x_f);
+            let x_t = if delta <= Float::abs(x_half - x_f) {
+                x_f + sigma * delta
+            } else {
+                x_half
+            };
+            let x_itp = if Float::abs(x_t - x_half) <= radius {
+                x_t
+            } else {
+                x_half - sigma * radius
+            };
+
+            let f_itp = self.eval_real(x_itp);
+            if f_itp.is_zero() {
+                return x_itp;
+            }
+            if Float::signum(f_itp) == Float::signum(f_lo) {
+                (lo, f_lo) = (x_itp, f_itp);
+            } else {
+                (hi, f_hi) = (x_itp, f_itp);
+            }
+            iter += T::one();
+        }
+        lo + (hi - lo) / two
+    }
+
+    /// Horner's method in real arithmetic
+    fn eval_real(&self, x: T) -> T {
+        self.iter().rev().fold(T::zero(), |acc, c| acc * x + c.re)
+    }
+
+    fn real_part(&self) -> Self {
+        Self::from_real_vec(self.iter().map(|c| c.re).collect()).normalize()
+    }
+
+    /// The monic square-free part of the real part, i.e. with the same roots
+    /// but all of them simple
+    fn square_free_part(&self) -> Self {
+        let p = self.real_part();
+        if p.len_raw() < 3 {
+            return p;
+        }
+        let tol = T::from_usize(100 * p.len_raw()).expect("infallible") * T::epsilon();
+        let (mut u, _) = p.gcd_cofactors(tol);
+        u.make_monic();
+        u.real_part()
+    }
+
+    fn max_coeff(&self) -> T {
+        self.iter().map(|&c| c.norm()).fold(T::zero(), Float::max)
+    }
+
+    /// Bound on the rounding errors in the remainder of dividing `self`, which
+    /// grow with the quotient
+    fn division_tolerance(&self, quot: &Self) -> T {
+        T::from_usize(4 * self.len_raw()).expect("infallible")
+            * T::epsilon()
+            * self.max_coeff()
+            * Float::max(quot.max_coeff(), T::one())
+    }
+
+    /// Discards the coefficients smaller than `tol`
+    fn chop(self, tol: T) -> Self {
+        Self::from_complex_vec(
+            self.iter()
+                .map(|&c| if c.norm() <= tol { Complex::zero() } else { c })
+                .collect(),
+        )
+        .normalize()
+    }
+
+    /// Upper bound on the number of roots in the open interval `(a, b)` by
+    /// Descartes' rule of signs, i.e. the number of sign changes in the
+    /// coefficients of `(1 + x)^n p((b + a x) / (1 + x))`, which maps the
+    /// positive real axis to `(a, b)`. The bound is exact if it is 0 or 1.
+    fn descartes_bound(&self, a: T, b: T) -> usize {
+        let n = self.len_raw();
+        // `p(a + (b - a) y)` with `y = 1 / (1 + x)`
+        let scaled = self
+            .clone()
+            .compose(Self::line(Complex::from(a), Complex::from(b - a)));
+        let mut reversed: Vec<_> = scaled.iter().copied().collect();
+        reversed.resize(n, Complex::zero());
+        reversed.reverse();
+        let transformed = Self::from_complex_vec(reversed)
+            .normalize()
+            .compose(Self::line(Complex::from(T::one()), Complex::from(T::one())));
+        let signs: Vec<_> = transformed
+            .clone()
+            .chop(
+                T::from_usize(4 * n).expect("infallible") * T::epsilon() * transformed.max_coeff(),
+            )
+            .iter()
+            .map(|c| c.re)
+            .filter(|c| !c.is_zero())
+            .collect();
+        signs.windows(2).filter(|w| w[0] * w[1] < T::zero()).count()
+    }
+}
+
+/// Bairstow's iteration for a factor `x^2 - r x - s` of the monic polynomial
+/// with real coefficients `coeffs`, of degree at least 3, starting from `r`
+/// and `s`. `None` if it does not converge within `max_iter` iterations.
+#[allow(clippy::many_single_char_names)]
+fn bairstow<T: Scalar + Float + RealField>(
+    coeffs: &[T],
+    mut r: T,
+    mut s: T,
+    epsilon: T,
+    max_iter: usize,
+) -> Option<(T, T)> {
+    let n = coeffs.len() - 1;
+    let tol = Float::max(epsilon, T::epsilon());
+    let mut b = vec![T::zero(); n + 1];
+    let mut c = vec![T::zero(); n + 1];
+    for _ in 0..max_iter {
+        // the quotient and remainder `b_1 (x - r) + b_0` of dividing by the
+        // quadratic, and the partial derivatives of the remainder with
+        // respect to `r` and `s` by dividing the quotient again
+        b[n] = coeffs[n];
+        b[n - 1] = coeffs[n - 1] + r * b[n];
+        for k in (0..n - 1).rev() {
+            b[k] = coeffs[k] + r * b[k + 1] + s * b[k + 2];
+        }
+        c[n] = b[n];
+        c[n - 1] = b[n - 1] + r * c[n];
+        for k in (1..n - 1).rev() {
+            c[k] = b[k] + r * c[k + 1] + s * c[k + 2];
+        }
+        if b[0].is_zero() && b[1].is_zero() {
+            return Some((r, s));
+        }
+
+        // Newton step on the remainder
+        let det = c[2] * c[2] - c[1] * c[3];
+        let dr = (c[3] * b[0] - c[2] * b[1]) / det;
+        let ds = (c[1] * b[1] - c[2] * b[0]) / det;
+        if !Float::is_finite(dr) || !Float::is_finite(ds) {
+            return None;
+        }
+        r += dr;
+        s += ds;
+
+        // relative to the magnitude of the roots of the factor
+        let scale = Float::max(Float::abs(r), Float::sqrt(Float::abs(s)));
+        if Float::abs(dr) <= tol * scale && Float::abs(ds) <= tol * scale * scale {
+            return Some((r, s));
+        }
+    }
+    None
+}
+
+/// The number of sign changes of the Sturm sequence at `x`, which may be
+/// infinite, ignoring zeros.
+fn sign_changes<T: Scalar + Float + RealField>(seq: &[Poly<T>], x: T) -> usize {
+    let signs: Vec<T> = seq
+        .iter()
+        .map(|p| {
+            if Float::is_finite(x) {
+                return p.eval_point_real(x).re;
+            }
+            // the sign of the leading term
+            let lead = p.last().re;
+            if x < T::zero() && p.len_raw().is_multiple_of(2) {
+                -lead
+            } else {
+                lead
+            }
+        })
+        .filter(|s| !s.is_zero())
+        .collect();
+    signs.windows(2).filter(|w| w[0] * w[1] < T::zero()).count()
+}
+
+#[cfg(test)]
+mod test {
+    use num::complex::Complex64;
+
+    use crate::{Poly, Poly64};
+
+    #[test]
+    fn sturm_sequence_of_cubic() {
+        // x^3 - x
+        let p = poly![0.0, -1.0, 0.0, 1.0];
+        let seq = p.sturm_sequence();
+        assert_eq!(seq.len(), 4);
+        assert_eq!(seq[0], p);
+        assert_eq!(seq[3].degree(), 0);
+    }
+
+    #[test]
+    fn count_real_roots_of_legendre() {
+        let p = Poly64::legendre(12);
+        assert_eq!(p.count_real_roots_in(-1.0, 1.0), 12);
+        assert_eq!(p.count_real_roots_in(0.0, 1.0), 6);
+        assert_eq!(p.count_real_roots_in(f64::NEG_INFINITY, f64::INFINITY), 12);
+    }
+
+    #[test]
+    fn count_real_roots_with_complex_pair() {
+        // (x^2 + 1)(x - 2)
+        let p = poly![-2.0, 1.0, -2.0, 1.0];
+        assert_eq!(p.count_real_roots_in(f64::NEG_INFINITY, f64::INFINITY), 1);
+        assert_eq!(p.count_real_roots_in(-10.0, 1.0), 0);
+    }
+
+    #[test]
+    fn isolate_close_roots() {
+        let roots = [-3.0, 0.1, 0.1001, 2.5, 2.5].map(|r| Complex64::new(r, 0.0));
+        let p = Poly::from_roots(&roots);
+        let intervals = p.isolate_real_roots(-5.0, 5.0);
+        assert_eq!(intervals.len(), 4);
+        for ((lo, hi), root) in intervals.iter().zip([-3.0, 0.1, 0.1001, 2.5]) {
+            assert!(*lo <= root && root <= *hi);
+            assert_eq!(p.count_real_roots_in(*lo, *hi), usize::from(lo != hi));
+        }
+        for w in intervals.windows(2) {
+            assert!(w[0].1 <= w[1].0);
+        }
+    }
+
+    #[test]
+    fn real_root_in_isolated_intervals() {
+        let p = Poly64::legendre(12);
+        let intervals = p.isolate_real_roots(-1.0, 1.0);
+        assert_eq!(intervals.len(), 12);
+        for (lo, hi) in intervals {
+            let root = p.real_root_in(lo, hi, 1E-14).unwrap();
+            assert!(lo <= root && root <= hi);
+            assert!(p.eval_point(Complex64::new(root, 0.0)).norm() < 1E-12);
+        }
+    }
+
+    #[test]
+    fn real_root_in_steep_bracket() {
+        // x^15 - 1e-9, regula falsi alone stalls on this
+        let mut coeffs = vec![0.0; 16];
+        coeffs[0] = -1E-9;
+        coeffs[15] = 1.0;
+        let p = Poly64::from_real_slice(&coeffs);
+        let root = p.real_root_in(0.0, 10.0, 1E-15).unwrap();
+        assert!((root - 1E-9f64.powf(1.0 / 15.0)).abs() < 1E-14);
+    }
+
+    #[test]
+    fn real_root_in_without_sign_change() {
+        let p = poly![1.0, 0.0, 1.0];
+        assert!(p.real_root_in(-1.0, 1.0, 1E-12).is_err());
+        assert!(p.real_root_in(0.0, f64::NAN, 1E-12).is_err());
+        let q = poly![-1.0, 1.0];
+        assert_eq!(q.real_root_in(1.0, 3.0, 1E-12).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn real_root_in_invalid_arguments() {
+        // the sign test alone would pass with infinite bounds
+        let p = poly![-2.0, 0.0, 1.0];
+        assert!(p.real_root_in(0.0, f64::INFINITY, 1E-12).is_err());
+        assert!(p.real_root_in(f64::NEG_INFINITY, 0.0, 1E-12).is_err());
+        assert!(p.real_root_in(0.0, 2.0, 0.0).is_err());
+        assert!(p.real_root_in(0.0, 2.0, -1E-12).is_err());
+        assert!(p.real_root_in(0.0, 2.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn conjugate_roots_of_reverse_bessel() {
+        // only complex roots for even degrees
+        let p = Poly64::reverse_bessel(24).unwrap();
+        let roots = p.try_conjugate_roots(1E-14, 100).unwrap();
+        assert!(roots.real.is_empty());
+        assert_eq!(roots.pairs.len(), 12);
+        for z in &roots.pairs {
+            assert!(z.im > 0.0);
+            assert!(
+                p.eval_point(*z).norm()
+                    < 1E-12 * p.eval_point(Complex64::new(z.norm(), 0.0)).norm()
+            );
+        }
+        let all = roots.to_complex();
+        assert_eq!(all.len(), 24);
+        for pair in all.chunks(2) {
+            assert_eq!(pair[0], pair[1].conj());
+        }
+    }
+
+    #[test]
+    fn conjugate_roots_of_legendre() {
+        let p = Poly64::legendre(12);
+        let mut roots = p.try_conjugate_roots(1E-14, 100).unwrap();
+        assert!(roots.pairs.is_empty());
+        roots.real.sort_by(f64::total_cmp);
+        let intervals = p.isolate_real_roots(-1.0, 1.0);
+        for (r, (lo, hi)) in roots.real.iter().zip(intervals) {
+            assert!(lo - 1E-12 <= *r && *r <= hi + 1E-12);
+        }
+    }
+
+    #[test]
+    fn conjugate_roots_with_zeros() {
+        // x^2 (x^2 + 1)
+        let p = poly![0.0, 0.0, 1.0, 0.0, 1.0];
+        let roots = p.try_conjugate_roots(1E-14, 100).unwrap();
+        assert_eq!(roots.real, vec![0.0, 0.0]);
+        assert_eq!(roots.pairs.len(), 1);
+        assert!((roots.pairs[0] - Complex64::new(0.0, 1.0)).norm() < 1E-15);
+        assert!(poly![3.0]
+            .try_conjugate_roots(1E-14, 100)
+            .unwrap()
+            .real
+            .is_empty());
+    }
+
+    #[test]
+    fn quadratic_factors_of_reverse_bessel() {
+        let p = Poly64::reverse_bessel(11).unwrap();
+        let factors = p.quadratic_factors(1E-14, 100).unwrap();
+        assert_eq!(factors.len(), 6);
+        assert!(factors[..5].iter().all(|f| f.degree() == 2));
+        assert_eq!(factors[5].degree(), 1);
+        let product = factors
+            .into_iter()
+            .fold(poly![p.last().re], |acc, f| acc * f);
+        let scale = p.iter().map(|c| c.norm()).fold(0.0, f64::max);
+        assert!((product - p).almost_zero(&(1E-12 * scale)));
+    }
+
+    #[test]
+    fn quadratic_factors_with_zeros() {
+        // 2 x^2 (x^2 + 1) (x^2 - 3x + 2)
+        let p: Poly64 = poly![0.0, 0.0, 4.0, -6.0, 6.0, -6.0, 2.0];
+        let factors = p.quadratic_factors(1E-14, 100).unwrap();
+        assert_eq!(factors[..2], [poly![0.0, 1.0], poly![0.0, 1.0]]);
+        assert_eq!(factors.len(), 4);
+        let mut quadratics: Vec<_> = factors[2..]
+            .iter()
+            .map(|f| (f.as_slice()[0].re, f.as_slice()[1].re))
+            .collect();
+        quadratics.sort_by(|a, b| a.1.total_cmp(&b.1));
+        assert!((quadratics[0].0 - 2.0).abs() < 1E-14 && (quadratics[0].1 + 3.0).abs() < 1E-14);
+        assert!((quadratics[1].0 - 1.0).abs() < 1E-14 && quadratics[1].1.abs() < 1E-14);
+        assert!(poly![2.0].quadratic_factors(1E-14, 100).unwrap().is_empty());
+    }
+}
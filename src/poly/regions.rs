@@ -0,0 +1,491 @@
+//! Counting and locating the roots in a region of the complex plane with the
+//! argument principle.
+
+use na::RealField;
+use num::{Complex, Float, Zero};
+
+use super::real_roots::SPLIT_POINTS;
+use crate::{Error, Poly, Scalar};
+
+/// Maximum number of times a piece of the contour is halved
+const MAX_REFINEMENTS: usize = 50;
+
+/// A rectangle with sides parallel to the axes, together with the number of
+/// roots inside, see [`Poly::locate_roots_in_rect`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RootRect<T> {
+    /// The corner with the smallest real and imaginary parts
+    pub lo: Complex<T>,
+
+    /// The corner with the largest real and imaginary parts
+    pub hi: Complex<T>,
+
+    /// The number of roots inside, counted with multiplicity
+    pub count: usize,
+}
+
+impl<T: Scalar + Float> RootRect<T> {
+    #[must_use]
+    pub fn center(&self) -> Complex<T> {
+        (self.lo + self.hi).unscale(T::one() + T::one())
+    }
+}
+
+impl<T: Scalar + RealField + Float> Poly<T> {
+    /// The number of roots in the open disk, counted with multiplicity.
+    ///
+    /// This is the winding number of the polynomial along the circle, which
+    /// is sampled more densely wherever the argument of the polynomial turns
+    /// quickly, so no roots need to be computed.
+    ///
+    /// # Errors
+    /// If the center or the radius is not finite, if the radius is negative,
+    /// or if a root is on the circle, or too close to it for the polynomial
+    /// to be evaluated accurately.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::Poly;
+    /// use num::Complex;
+    ///
+    /// let p = Poly::from_roots(&[
+    ///     Complex::new(0.5, 0.0),
+    ///     Complex::new(0.0, -0.9),
+    ///     Complex::new(2.0, 0.0),
+    /// ]);
+    /// assert_eq!(p.count_roots_in_disk(Complex::new(0.0, 0.0), 1.0).unwrap(), 2);
+    /// assert_eq!(p.count_roots_in_disk(Complex::new(2.0, 0.0), 0.1).unwrap(), 1);
+    /// assert!(p.count_roots_in_disk(Complex::new(0.0, 0.0), 0.5).is_err());
+    /// ```
+    pub fn count_roots_in_disk(&self, center: Complex<T>, radius: T) -> Result<usize, Error> {
+        debug_assert!(self.is_normalized());
+
+        check_disk(center, radius)?;
+        self.winding_number(|t| center + Complex::from_polar(radius, t * T::two_pi()))
+    }
+
+    /// The number of roots in the open rectangle with opposite corners `a`
+    /// and `b`, counted with multiplicity, see [`Poly::count_roots_in_disk`].
+    ///
+    /// A half-plane can be checked with a rectangle that extends beyond
+    /// [`Poly::root_annulus`].
+    ///
+    /// # Errors
+    /// If a corner is not finite, or if a root is on the boundary, or too
+    /// close to it for the polynomial to be evaluated accurately.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{Poly64, RootBounds};
+    /// use num::Complex;
+    ///
+    /// // a stable filter has all of its poles in the left half-plane
+    /// let p = Poly64::reverse_bessel(8).unwrap();
+    /// let r = 2.0 * p.root_annulus(RootBounds::Tight).outer;
+    /// let left = p.count_roots_in_rect(Complex::new(-r, -r), Complex::new(0.0, r));
+    /// assert_eq!(left.unwrap(), 8);
+    /// ```
+    pub fn count_roots_in_rect(&self, a: Complex<T>, b: Complex<T>) -> Result<usize, Error> {
+        debug_assert!(self.is_normalized());
+
+        check_finite(&[a, b])?;
+        let (lo, hi) = corners(a, b);
+        self.winding_number(|t| rect_boundary(lo, hi, t))
+    }
+
+    /// Rectangles no wider or taller than `tol` that contain all roots in the
+    /// open rectangle with opposite corners `a` and `b`, found by recursively
+    /// splitting it into quarters and discarding the ones without roots, see
+    /// [`Poly::count_roots_in_rect`].
+    ///
+    /// Every rectangle contains at least one root, more than one only if
+    /// they are closer than `tol`.
+    ///
+    /// # Errors
+    /// If a corner is not finite, if `tol` is not positive and finite, if a
+    /// root is on the boundary of the rectangle, or if the rectangles can't be split without a root
+    /// being too close to the new boundaries.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::Poly;
+    /// use num::Complex;
+    ///
+    /// let roots = [Complex::new(0.3, 0.2), Complex::new(-0.6, -0.1)];
+    /// let p = Poly::from_roots(&roots);
+    /// let rects = p
+    ///     .locate_roots_in_rect(Complex::new(-1.0, -1.0), Complex::new(1.0, 1.0), 1E-6)
+    ///     .unwrap();
+    /// assert_eq!(rects.len(), 2);
+    /// assert!(rects.iter().all(|r| r.count == 1));
+    /// assert!(roots.iter().all(|z| rects.iter().any(|r| (r.center() - z).norm() < 1E-6)));
+    /// ```
+    pub fn locate_roots_in_rect(
+        &self,
+        a: Complex<T>,
+        b: Complex<T>,
+        tol: T,
+    ) -> Result<Vec<RootRect<T>>, Error> {
+        debug_assert!(self.is_normalized());
+
+        check_finite(&[a, b])?;
+        if !Float::is_finite(tol) || tol <= T::zero() {
+            return Err(Error::invalid_tolerance());
+        }
+        let (lo, hi) = corners(a, b);
+        self.locate_roots(lo, hi, tol, |_| true)
+    }
+
+    /// Rectangles no wider or taller than `tol` that contain all roots in the
+    /// open disk, see [`Poly::locate_roots_in_rect`] and
+    /// [`Poly::count_roots_in_disk`].
+    ///
+    /// The square around the disk is split into quarters, discarding the ones
+    /// that are outside of the disk. The rectangles that cross the circle may
+    /// also contain roots just outside of it, closer than `tol`.
+    ///
+    /// # Errors
+    /// If the center or the radius is not finite, if the radius is negative,
+    /// if `tol` is not positive and finite, if a root is on the boundary of
+    /// the square around the disk, or if the rectangles can't be split
+    /// without a root being too close to the new boundaries.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::Poly;
+    /// use num::Complex;
+    ///
+    /// let roots = [Complex::new(0.3, 0.2), Complex::new(0.8, 0.8)];
+    /// let p = Poly::from_roots(&roots);
+    /// let rects = p.locate_roots_in_disk(Complex::new(0.0, 0.0), 1.0, 1E-6).unwrap();
+    /// assert_eq!(rects.len(), 1);
+    /// assert!((rects[0].center() - roots[0]).norm() < 1E-6);
+    /// ```
+    pub fn locate_roots_in_disk(
+        &self,
+        center: Complex<T>,
+        radius: T,
+        tol: T,
+    ) -> Result<Vec<RootRect<T>>, Error> {
+        debug_assert!(self.is_normalized());
+
+        check_disk(center, radius)?;
+        if !Float::is_finite(tol) || tol <= T::zero() {
+            return Err(Error::invalid_tolerance());
+        }
+        let corner = Complex::new(radius, radius);
+        self.locate_roots(center - corner, center + corner, tol, |rect| {
+            // the point of the rectangle closest to the center
+            let closest = Complex::new(
+                Float::min(Float::max(center.re, rect.lo.re), rect.hi.re),
+                Float::min(Float::max(center.im, rect.lo.im), rect.hi.im),
+            );
+            (closest - center).norm() < radius
+        })
+    }
+}
+
+// private
+impl<T: Scalar + RealField + Float> Poly<T> {
+    /// Splits the rectangle from `lo` to `hi` until the pieces with roots
+    /// are no larger than `tol`, only keeping the pieces for which `keep` is
+    /// true
+    fn locate_roots(
+        &self,
+        lo: Complex<T>,
+        hi: Complex<T>,
+        tol: T,
+        keep: impl Fn(&RootRect<T>) -> bool,
+    ) -> Result<Vec<RootRect<T>>, Error> {
+        let count = self.count_roots_in_rect(lo, hi)?;
+        let mut stack = vec![RootRect { lo, hi, count }];
+        let mut found = vec![];
+        while let Some(rect) = stack.pop() {
+            if rect.count == 0 || !keep(&rect) {
+                continue;
+            }
+            let size = rect.hi - rect.lo;
+            if Float::max(size.re, size.im) <= tol {
+                found.push(rect);
+                continue;
+            }
+            stack.extend(self.split_rect(rect)?);
+        }
+        Ok(found)
+    }
+
+    /// The winding number of the polynomial along the closed curve `path`,
+    /// parametrized on `[0, 1]` counterclockwise
+    fn winding_number(&self, path: impl Fn(T) -> Complex<T>) -> Result<usize, Error> {
+        if self.len_raw() < 2 {
+            return Ok(0);
+        }
+
+        // a few samples per root to begin with, so the refinement has
+        // something to go by
+        let pieces = 4 * self.len_raw();
+        let step = Float::recip(T::from_usize(pieces).expect("infallible"));
+        let start = self.eval_on_contour(path(T::zero()))?;
+        let (mut t0, mut p0) = (T::zero(), start);
+        let mut total = T::zero();
+        for k in 1..=pieces {
+            // the last piece ends exactly at the start, so the total is a
+            // multiple of `2 pi` up to rounding
+            let (t1, p1) = if k == pieces {
+                (T::one(), start)
+            } else {
+                let t1 = step * T::from_usize(k).expect("infallible");
+                (t1, self.eval_on_contour(path(t1))?)
+            };
+            total += self.arg_change(&path, (t0, p0), (t1, p1), MAX_REFINEMENTS)?;
+            (t0, p0) = (t1, p1);
+        }
+        let winding = Float::round(total / T::two_pi());
+        Ok(Float::max(winding, T::zero())
+            .to_usize()
+            .expect("winding number is finite"))
+    }
+
+    /// The change of the argument of the polynomial along `path` from `t0`
+    /// to `t1`, where the polynomial takes the values `p0` and `p1`, halving
+    /// the piece until the argument turns by less than an eighth of a turn on
+    /// each half
+    fn arg_change(
+        &self,
+        path: &impl Fn(T) -> Complex<T>,
+        (t0, p0): (T, Complex<T>),
+        (t1, p1): (T, Complex<T>),
+        refinements: usize,
+    ) -> Result<T, Error> {
+        let half = Float::recip(T::from_u8(2).expect("infallible"));
+        let tm = (t0 + t1) * half;
+        let pm = self.eval_on_contour(path(tm))?;
+        let left = (pm / p0).arg();
+        let right = (p1 / pm).arg();
+        if Float::abs(left) < T::frac_pi_4() && Float::abs(right) < T::frac_pi_4() {
+            return Ok(left + right);
+        }
+        if refinements == 0 {
+            return Err(Error::root_on_contour());
+        }
+        Ok(self.arg_change(path, (t0, p0), (tm, pm), refinements - 1)?
+            + self.arg_change(path, (tm, pm), (t1, p1), refinements - 1)?)
+    }
+
+    /// The value of the polynomial at a point of a contour, which must be
+    /// larger than the rounding errors of Horner's method for its argument to
+    /// be meaningful
+    fn eval_on_contour(&self, z: Complex<T>) -> Result<Complex<T>, Error> {
+        if !Float::is_finite(z.re) || !Float::is_finite(z.im) {
+            return Err(Error::not_finite());
+        }
+        let (value, magnitude) = self
+            .iter()
+            .rev()
+            .fold((Complex::zero(), T::zero()), |(value, magnitude), &c| {
+                (value * z + c, magnitude * z.norm() + c.norm())
+            });
+        let bound = T::from_usize(4 * self.len_raw()).expect("infallible") * T::epsilon();
+        if value.norm() <= bound * magnitude {
+            return Err(Error::root_on_contour());
+        }
+        Ok(value)
+    }
+
+    /// Splits the rectangle into quarters and counts the roots in each, the
+    /// split point is moved if a root is too close to the new boundaries
+    fn split_rect(&self, rect: RootRect<T>) -> Result<Vec<RootRect<T>>, Error> {
+        let RootRect { lo, hi, count } = rect;
+        for &f in &SPLIT_POINTS {
+            let mid = lo + (hi - lo).scale(T::from_f64(f).expect("infallible"));
+            let quarters = [
+                (lo, mid),
+                (Complex::new(mid.re, lo.im), Complex::new(hi.re, mid.im)),
+                (Complex::new(lo.re, mid.im), Complex::new(mid.re, hi.im)),
+                (mid, hi),
+            ];
+            let Ok(children) = quarters
+                .iter()
+                .map(|&(lo, hi)| {
+                    self.count_roots_in_rect(lo, hi)
+                        .map(|count| RootRect { lo, hi, count })
+                })
+                .collect::<Result<Vec<_>, _>>()
+            else {
+                continue;
+            };
+            if children.iter().map(|r| r.count).sum::<usize>() == count {
+                return Ok(children);
+            }
+        }
+        Err(Error::root_on_contour())
+    }
+}
+
+/// Rejects infinite and NaN points before the polynomial is evaluated on
+/// them, where the comparisons with the rounding errors would fail
+fn check_finite<T: Scalar + Float>(points: &[Complex<T>]) -> Result<(), Error> {
+    if points
+        .iter()
+        .all(|z| Float::is_finite(z.re) && Float::is_finite(z.im))
+    {
+        Ok(())
+    } else {
+        Err(Error::not_finite())
+    }
+}
+
+/// Rejects disks with a non-finite center or radius, or a negative radius
+fn check_disk<T: Scalar + Float>(center: Complex<T>, radius: T) -> Result<(), Error> {
+    check_finite(&[center, Complex::new(radius, T::zero())])?;
+    if radius < T::zero() {
+        return Err(Error::negative_radius());
+    }
+    Ok(())
+}
+
+/// The corners with the smallest and the largest coordinates
+fn corners<T: Scalar + Float>(a: Complex<T>, b: Complex<T>) -> (Complex<T>, Complex<T>) {
+    (
+        Complex::new(Float::min(a.re, b.re), Float::min(a.im, b.im)),
+        Complex::new(Float::max(a.re, b.re), Float::max(a.im, b.im)),
+    )
+}
+
+/// The boundary of the rectangle counterclockwise from `lo`, one side for
+/// every quarter of `t` in `[0, 1]`
+fn rect_boundary<T: Scalar + Float>(lo: Complex<T>, hi: Complex<T>, t: T) -> Complex<T> {
+    let t = t * T::from_u8(4).expect("infallible");
+    let side = Float::min(Float::floor(t), T::from_u8(3).expect("infallible"));
+    let s = t - side;
+    let (from, to) = match side.to_u8() {
+        Some(0) => (lo, Complex::new(hi.re, lo.im)),
+        Some(1) => (Complex::new(hi.re, lo.im), hi),
+        Some(2) => (hi, Complex::new(lo.re, hi.im)),
+        _ => (Complex::new(lo.re, hi.im), lo),
+    };
+    from + (to - from).scale(s)
+}
+
+#[cfg(test)]
+mod test {
+    use num::complex::Complex64;
+
+    use crate::{error::ErrorKind, Error, Poly, Poly64};
+
+    #[test]
+    fn count_in_disk_with_multiplicity() {
+        let roots = [
+            Complex64::new(0.1, 0.1),
+            Complex64::new(0.1, 0.1),
+            Complex64::new(0.1, 0.1),
+            Complex64::new(-0.7, 0.5),
+            Complex64::new(3.0, -4.0),
+        ];
+        let p = Poly::from_roots(&roots);
+        let origin = Complex64::new(0.0, 0.0);
+        assert_eq!(p.count_roots_in_disk(origin, 0.5).unwrap(), 3);
+        assert_eq!(p.count_roots_in_disk(origin, 1.0).unwrap(), 4);
+        assert_eq!(p.count_roots_in_disk(origin, 10.0).unwrap(), 5);
+        assert_eq!(
+            p.count_roots_in_disk(Complex64::new(3.0, -4.0), 1E-3)
+                .unwrap(),
+            1
+        );
+        assert!(p.count_roots_in_disk(origin, 5.0).is_err());
+    }
+
+    #[test]
+    fn count_in_rect() {
+        // roots of unity of order 12, none on the axes but 1, i, -1, -i
+        let mut coeffs = vec![0.0; 13];
+        coeffs[0] = -1.0;
+        coeffs[12] = 1.0;
+        let p = Poly64::from_real_slice(&coeffs);
+        let first_quadrant =
+            p.count_roots_in_rect(Complex64::new(0.0, 0.0), Complex64::new(2.0, 2.0));
+        assert!(first_quadrant.is_err());
+        let open_quadrant =
+            p.count_roots_in_rect(Complex64::new(0.01, 0.01), Complex64::new(2.0, 2.0));
+        assert_eq!(open_quadrant.unwrap(), 2);
+        // corners may be given in any order
+        let everything =
+            p.count_roots_in_rect(Complex64::new(2.0, -2.0), Complex64::new(-2.0, 2.0));
+        assert_eq!(everything.unwrap(), 12);
+    }
+
+    #[test]
+    fn locate_clustered_roots() {
+        let roots = [
+            Complex64::new(0.5, 0.5),
+            Complex64::new(0.5 + 1E-4, 0.5),
+            Complex64::new(-0.25, 0.75),
+        ];
+        let p = Poly::from_roots(&roots);
+        let rects = p
+            .locate_roots_in_rect(Complex64::new(-1.0, -1.0), Complex64::new(1.0, 1.0), 1E-6)
+            .unwrap();
+        assert_eq!(rects.len(), 3);
+        for z in roots {
+            assert!(rects
+                .iter()
+                .any(|r| r.lo.re <= z.re && z.re <= r.hi.re && r.lo.im <= z.im && z.im <= r.hi.im));
+        }
+    }
+
+    #[test]
+    fn locate_in_disk() {
+        let roots = [
+            Complex64::new(0.1, -0.2),
+            Complex64::new(-0.6, 0.6),
+            Complex64::new(0.9, 0.9),
+            Complex64::new(-0.95, -0.95),
+        ];
+        let p = Poly::from_roots(&roots);
+        let rects = p
+            .locate_roots_in_disk(Complex64::new(0.0, 0.0), 1.0, 1E-6)
+            .unwrap();
+        // the last two are in the square around the unit disk, but not in it
+        assert_eq!(rects.len(), 2);
+        for z in &roots[..2] {
+            assert!(rects.iter().any(|r| (r.center() - z).norm() < 1E-6));
+        }
+    }
+
+    #[test]
+    fn locate_invalid_tolerance() {
+        let p = Poly::from_roots(&[Complex64::new(0.5, 0.5)]);
+        let (a, b) = (Complex64::new(-1.0, -1.0), Complex64::new(1.0, 1.0));
+        for tol in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(p.locate_roots_in_rect(a, b, tol).is_err());
+            assert!(p
+                .locate_roots_in_disk(Complex64::new(0.0, 0.0), 1.0, tol)
+                .is_err());
+        }
+    }
+
+    fn not_finite<T>(result: Result<T, Error>) -> bool {
+        matches!(result, Err(e) if matches!(e.source, ErrorKind::NotFinite))
+    }
+
+    fn negative<T>(result: Result<T, Error>) -> bool {
+        matches!(result, Err(e) if matches!(e.source, ErrorKind::NegativeRadius))
+    }
+
+    #[test]
+    fn invalid_regions() {
+        let p = Poly::from_roots(&[Complex64::new(0.5, 0.5)]);
+        let origin = Complex64::new(0.0, 0.0);
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let z = Complex64::new(bad, 0.0);
+            assert!(not_finite(p.count_roots_in_disk(z, 1.0)));
+            assert!(not_finite(p.count_roots_in_disk(origin, bad)));
+            assert!(not_finite(p.count_roots_in_rect(z, origin)));
+            assert!(not_finite(p.locate_roots_in_rect(origin, z, 1E-6)));
+            assert!(not_finite(p.locate_roots_in_disk(z, 1.0, 1E-6)));
+        }
+        assert!(negative(p.count_roots_in_disk(origin, -1.0)));
+        assert!(negative(p.locate_roots_in_disk(origin, -1.0, 1E-6)));
+        assert_eq!(p.count_roots_in_disk(origin, 0.0).unwrap(), 0);
+    }
+}
@@ -0,0 +1,411 @@
+//! Configurable root finding, see [`RootFinder`].
+
+use na::{Complex, ComplexField, RealField};
+use num::{Float, One, Zero};
+
+use crate::{AllRootsAlgorithms, ErrorKind, OneRootAlgorithms, PolishAlgorithms, Poly, Scalar};
+
+/// Finds all the roots of a polynomial with an [`AllRootsAlgorithms`],
+/// falling back to a [`OneRootAlgorithms`] to deflate the polynomial by one
+/// root at a time when it gets stuck.
+///
+/// All settings have defaults, so only the ones that matter need to be set.
+///
+/// # Examples
+/// ```
+/// use rust_poly::{AllRootsAlgorithms, Poly64, RootFinder};
+///
+/// let p = Poly64::legendre(8);
+/// let report = RootFinder::new()
+///     .epsilon(1E-14)
+///     .algorithm(AllRootsAlgorithms::Aberth)
+///     .polish_iter(2)
+///     .run(&p)
+///     .unwrap();
+/// assert_eq!(report.roots.len(), 8);
+/// assert!(!report.recovered);
+/// assert!(report.roots.iter().all(|r| r.residual < 1E-10));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RootFinder<T: Scalar> {
+    epsilon: T,
+    max_iter: usize,
+    max_tries: usize,
+    max_recovery_iter: Option<usize>,
+    algorithm: AllRootsAlgorithms,
+    recovery_algorithm: OneRootAlgorithms,
+    initial_guesses: Vec<Complex<T>>,
+    polish_iter: usize,
+    polish_algorithm: PolishAlgorithms,
+}
+
+/// Which algorithm found a root, see [`FoundRoot`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RootSource {
+    /// Found together with the other roots
+    AllRoots(AllRootsAlgorithms),
+
+    /// Found on its own, to deflate the polynomial after the all-roots
+    /// algorithm got stuck
+    Recovery(OneRootAlgorithms),
+
+    /// Solved with the linear or quadratic formula, because the polynomial
+    /// or what was left of it after deflation has degree two or less
+    ClosedForm,
+}
+
+/// A root found by [`RootFinder::run`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FoundRoot<T> {
+    pub root: Complex<T>,
+
+    /// The modulus of the original polynomial evaluated at the root, in
+    /// compensated arithmetic
+    pub residual: T,
+
+    /// The residual before polishing, the same as `residual` if polishing is
+    /// disabled
+    pub unpolished_residual: T,
+
+    /// The number of iterations the algorithm took, if it keeps track of it.
+    /// Simultaneous methods count the iterations until this root converged.
+    pub iterations: Option<usize>,
+
+    pub source: RootSource,
+}
+
+/// The result of [`RootFinder::run`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RootReport<T> {
+    pub roots: Vec<FoundRoot<T>>,
+
+    /// The all-roots algorithm got stuck at least once, so some roots were
+    /// found by the recovery algorithm
+    pub recovered: bool,
+}
+
+impl<T> RootReport<T> {
+    /// Discard everything but the roots
+    #[must_use]
+    pub fn into_roots(self) -> Vec<Complex<T>> {
+        self.roots.into_iter().map(|r| r.root).collect()
+    }
+}
+
+impl<T: Scalar + RealField + Float> Default for RootFinder<T> {
+    fn default() -> Self {
+        Self {
+            epsilon: T::epsilon() * T::from_u8(100).expect("infallible"),
+            max_iter: 1000,
+            max_tries: 10,
+            max_recovery_iter: None,
+            algorithm: AllRootsAlgorithms::FrancisQR,
+            recovery_algorithm: OneRootAlgorithms::Newton,
+            initial_guesses: vec![],
+            polish_iter: 0,
+            polish_algorithm: PolishAlgorithms::Newton,
+        }
+    }
+}
+
+impl<T: Scalar + RealField + Float> RootFinder<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tolerance of the stopping criterion, its exact meaning depends on the
+    /// algorithm
+    #[must_use]
+    pub const fn epsilon(mut self, epsilon: T) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Iteration budget of the all-roots algorithm for each try
+    #[must_use]
+    pub const fn max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// How many times the all-roots algorithm is run, recovering one root in
+    /// between every time it gets stuck
+    #[must_use]
+    pub const fn max_tries(mut self, max_tries: usize) -> Self {
+        self.max_tries = max_tries;
+        self
+    }
+
+    /// Iteration budget of the recovery algorithm for each root, the same as
+    /// [`RootFinder::max_iter`] if not set
+    #[must_use]
+    pub const fn max_recovery_iter(mut self, max_recovery_iter: usize) -> Self {
+        self.max_recovery_iter = Some(max_recovery_iter);
+        self
+    }
+
+    #[must_use]
+    pub const fn algorithm(mut self, algorithm: AllRootsAlgorithms) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    #[must_use]
+    pub const fn recovery_algorithm(mut self, recovery_algorithm: OneRootAlgorithms) -> Self {
+        self.recovery_algorithm = recovery_algorithm;
+        self
+    }
+
+    /// Starting points, one per root. They are used in order by the recovery
+    /// algorithm, and the remaining ones by the simultaneous all-roots
+    /// algorithms, i.e. [`AllRootsAlgorithms::Aberth`] and
+    /// [`AllRootsAlgorithms::DurandKerner`]. Missing guesses are filled in
+    /// automatically.
+    #[must_use]
+    pub fn initial_guesses(mut self, initial_guesses: &[Complex<T>]) -> Self {
+        self.initial_guesses = initial_guesses.to_vec();
+        self
+    }
+
+    /// Maximum number of steps refining each root against the original
+    /// polynomial after all roots are found, see [`Poly::polish_roots`].
+    /// `0` (the default) disables polishing.
+    #[must_use]
+    pub const fn polish_iter(mut self, polish_iter: usize) -> Self {
+        self.polish_iter = polish_iter;
+        self
+    }
+
+    /// [`PolishAlgorithms::Newton`] by default
+    #[must_use]
+    pub const fn polish_algorithm(mut self, polish_algorithm: PolishAlgorithms) -> Self {
+        self.polish_algorithm = polish_algorithm;
+        self
+    }
+
+    /// Find the roots of `poly`.
+    ///
+    /// # Errors
+    /// If the algorithms don't converge within the given budgets, the error
+    /// contains the roots that were found, which are fewer than the degree.
+    pub fn run(&self, poly: &Poly<T>) -> Result<RootReport<T>, RootReport<T>> {
+        debug_assert!(poly.is_normalized());
+
+        let mut found = vec![];
+        let mut recovered = false;
+        if poly.len_raw() < 2 {
+            return Ok(self.report(poly, found, recovered));
+        }
+
+        let max_recovery_iter = self.max_recovery_iter.unwrap_or(self.max_iter);
+        let mut guesses = self.initial_guesses.as_slice();
+        let mut this = poly.clone();
+
+        // a small safe number, this is often done in LAPACK to avoid overflows
+        let small_num = Float::sqrt(T::min_positive_value()) / T::epsilon();
+
+        // leading zero coefficients can be problematic, so shifting to
+        // avoid (the householder reflectors cannot be constructed if the
+        // first element is zero), the other algorithms don't need this
+        let needs_shifting = !matches!(
+            self.algorithm,
+            AllRootsAlgorithms::Aberth
+                | AllRootsAlgorithms::DurandKerner
+                | AllRootsAlgorithms::StructuredQR
+        );
+
+        for _ in 0..self.max_tries {
+            if this.len_raw() <= 3 {
+                break;
+            }
+            let shift = if needs_shifting && this.0[0].abs() < small_num {
+                Complex::one()
+            } else {
+                Complex::zero()
+            };
+            let shifted = if shift.is_zero() {
+                this.clone()
+            } else {
+                this.clone().translate(shift, Complex::zero())
+            };
+            match shifted.all_roots(self.algorithm, guesses, self.epsilon, self.max_iter) {
+                Ok(roots) => {
+                    found.extend(roots.into_iter().map(|(r, iterations)| {
+                        (r - shift, iterations, RootSource::AllRoots(self.algorithm))
+                    }));
+                    return Ok(self.report(poly, found, recovered));
+                }
+                // only recover if MaxIterInner
+                Err(err) if !matches!(err.source, ErrorKind::MaxIterInner) => break,
+                Err(_) => {}
+            }
+
+            // uses one iteration of single-root algorithm for recovery when
+            // the multi-root algorithm gets stuck (for pathological cases like
+            // Legendre polynomials), this shrinks the problem by 1 degree
+            // and moves around the coefficients so they are not pathologic anymore
+            recovered = true;
+            let Ok((r, iterations)) = this.one_root(
+                self.recovery_algorithm,
+                guesses.first().copied(),
+                self.epsilon,
+                max_recovery_iter,
+            ) else {
+                break;
+            };
+            guesses = guesses.get(1..).unwrap_or_default();
+            found.push((r, iterations, RootSource::Recovery(self.recovery_algorithm)));
+            this = this.deflate_linear(r).0;
+        }
+
+        // the iterative algorithms expect at least a cubic
+        if this.len_raw() <= 3 {
+            found.extend(
+                this.roots_closed_form()
+                    .into_iter()
+                    .map(|r| (r, None, RootSource::ClosedForm)),
+            );
+            return Ok(self.report(poly, found, recovered));
+        }
+        Err(self.report(poly, found, recovered))
+    }
+}
+
+// private
+impl<T: Scalar + RealField + Float> RootFinder<T> {
+    fn report(
+        &self,
+        poly: &Poly<T>,
+        found: Vec<(Complex<T>, Option<usize>, RootSource)>,
+        recovered: bool,
+    ) -> RootReport<T> {
+        let approximations: Vec<_> = found.iter().map(|(root, _, _)| *root).collect();
+        let polished = poly.polish_roots(&approximations, self.polish_algorithm, self.polish_iter);
+        let roots = found
+            .into_iter()
+            .zip(polished)
+            .map(|((_, iterations, source), polished)| FoundRoot {
+                root: polished.root,
+                residual: polished.residual,
+                unpolished_residual: polished.initial_residual,
+                iterations,
+                source,
+            })
+            .collect();
+        RootReport { roots, recovered }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num::complex::Complex64;
+
+    use crate::{
+        poly, AllRootsAlgorithms, OneRootAlgorithms, Poly, Poly64, RootFinder, RootSource,
+    };
+
+    #[test]
+    fn aberth_reports_iterations() {
+        let p = Poly64::legendre(10);
+        let report = RootFinder::new()
+            .algorithm(AllRootsAlgorithms::Aberth)
+            .run(&p)
+            .unwrap();
+        assert_eq!(report.roots.len(), 10);
+        assert!(!report.recovered);
+        for r in &report.roots {
+            assert_eq!(r.source, RootSource::AllRoots(AllRootsAlgorithms::Aberth));
+            assert!(r.iterations.is_some_and(|i| i > 0));
+            assert!(r.residual < 1E-10);
+        }
+    }
+
+    #[test]
+    fn exact_initial_guesses() {
+        let roots = [1.0, -2.0, 3.0].map(|r| Complex64::new(r, 0.0));
+        let p = Poly::from_roots(&roots);
+        let report = RootFinder::new()
+            .algorithm(AllRootsAlgorithms::DurandKerner)
+            .initial_guesses(&roots)
+            .run(&p)
+            .unwrap();
+        assert!(report.roots.iter().all(|r| r.iterations == Some(0)));
+        assert_eq!(report.into_roots(), roots);
+    }
+
+    #[test]
+    fn recovery() {
+        let p = Poly64::legendre(8);
+        let report = RootFinder::new()
+            .epsilon(1E-12)
+            .algorithm(AllRootsAlgorithms::Aberth)
+            .max_iter(3)
+            .max_recovery_iter(100)
+            .max_tries(20)
+            .recovery_algorithm(OneRootAlgorithms::Halley)
+            .run(&p)
+            .unwrap();
+        assert!(report.recovered);
+        assert_eq!(report.roots.len(), 8);
+        assert_eq!(
+            report.roots[0].source,
+            RootSource::Recovery(OneRootAlgorithms::Halley)
+        );
+        assert!(report.roots.iter().all(|r| r.residual < 1E-10));
+    }
+
+    #[test]
+    fn quadratic_in_closed_form() {
+        let report = RootFinder::new().run(&poly![1.0, 0.0, 1.0]).unwrap();
+        assert!(!report.recovered);
+        assert!(report
+            .roots
+            .iter()
+            .all(|r| r.source == RootSource::ClosedForm));
+        let roots = report.into_roots();
+        assert_eq!(roots.len(), 2);
+        for e in [Complex64::i(), -Complex64::i()] {
+            assert!(roots.iter().any(|r| (r - e).norm() < 1E-15));
+        }
+        let report = RootFinder::new().run(&poly![-2.0, 4.0]).unwrap();
+        assert_eq!(report.into_roots(), [Complex64::new(0.5, 0.0)]);
+    }
+
+    #[test]
+    fn recovery_down_to_quadratic() {
+        // (z - 2)(z^2 + 1), the all-roots algorithm gives up right away
+        let expected = [Complex64::new(2.0, 0.0), Complex64::i(), -Complex64::i()];
+        let p = Poly::from_roots(&expected);
+        let report = RootFinder::new()
+            .algorithm(AllRootsAlgorithms::Aberth)
+            .max_iter(0)
+            .max_recovery_iter(100)
+            .run(&p)
+            .unwrap();
+        assert!(report.recovered);
+        assert_eq!(
+            report.roots[0].source,
+            RootSource::Recovery(OneRootAlgorithms::Newton)
+        );
+        assert!(report.roots[1..]
+            .iter()
+            .all(|r| r.source == RootSource::ClosedForm));
+        assert!(report.roots.iter().all(|r| r.residual < 1E-12));
+        let roots = report.into_roots();
+        for e in expected {
+            assert!(roots.iter().any(|r| (r - e).norm() < 1E-12));
+        }
+    }
+
+    #[test]
+    fn polishing_reduces_residuals() {
+        let p = Poly64::legendre(16);
+        let finder = RootFinder::new().algorithm(AllRootsAlgorithms::MultishiftQR);
+        let rough = finder.run(&p).unwrap();
+        let polished = finder.polish_iter(3).run(&p).unwrap();
+        for (r, q) in rough.roots.iter().zip(&polished.roots) {
+            assert!(q.residual <= r.residual);
+        }
+    }
+}
@@ -2,28 +2,70 @@ use na::{Complex, ComplexField, Normed, RealField, Scalar};
 use num::{traits::float::FloatCore, Float, FromPrimitive, Num, One, Zero};
 
 use crate::{
-    Error, ErrorKind, Poly, ScalarOps,
     __util::{
         self,
         casting::usize_to_scalar,
+        compensated::horner_compensated_derivatives,
         complex::{c_min, c_neg, complex_sort_mut},
     },
+    Error, Poly, RootFinder, RootReport, ScalarOps,
 };
 
+use super::bounds::cauchy_root;
+
 /// Polynomial root-finding algorithms
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum OneRootAlgorithms {
     Newton,
     Halley,
     JenkinsTraub,
+
+    /// Laguerre's method, which converges cubically to simple roots, and
+    /// from any starting point if all roots are real.
+    Laguerre,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum AllRootsAlgorithms {
     #[deprecated]
     Schur,
 
     FrancisQR,
+
+    /// Small-bulge multishift QR with aggressive early deflation, better
+    /// suited for large polynomials than [`AllRootsAlgorithms::FrancisQR`]
+    MultishiftQR,
+
+    /// Aberth-Ehrlich simultaneous iteration, starting from initial guesses
+    /// given by the Newton polygon. Runs in `O(n^2)` time per iteration.
+    Aberth,
+
+    /// Durand-Kerner (Weierstrass) simultaneous iteration, simpler but slower
+    /// to converge than [`AllRootsAlgorithms::Aberth`]. Useful as an
+    /// independent cross-check of the other algorithms.
+    DurandKerner,
+
+    /// Single shift QR on the companion matrix, exploiting its
+    /// unitary-plus-rank-one structure like the AMVW algorithm. Runs in
+    /// `O(n^2)` time and `O(n)` memory, and it is backward stable.
+    StructuredQR,
+}
+
+/// A disk in the complex plane that is guaranteed to contain at least one
+/// root of a polynomial, see [`Poly::root_inclusion_disks`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InclusionDisk<T> {
+    /// The approximation of the root
+    pub center: Complex<T>,
+
+    /// An upper bound on the distance to the nearest root
+    pub radius: T,
+
+    /// The disk does not intersect any other disk, so it contains exactly one
+    /// root, i.e. the root is certified to be simple and isolated
+    pub isolated: bool,
 }
 
 // private
@@ -34,9 +76,8 @@ impl<T: Scalar + RealField + Float> Poly<T> {
         debug_assert!(self.len_raw() >= 2);
 
         let small = Float::recip(usize_to_scalar::<T>(1_000));
-        let p_diff = self.clone().diff();
-        let mut pz = self.eval_point(Complex::zero());
-        let mut pdz = p_diff.eval_point(Complex::zero());
+        let mut pz = self.0[0];
+        let mut pdz = self.0[1];
 
         // avoid divide by zero
         if pdz.norm() < small {
@@ -69,20 +110,33 @@ impl<T: Scalar + RealField + Float> Poly<T> {
         guess
     }
 
+    /// Writes the accurate value at `x` and the derivatives to `out`, like
+    /// [`Poly::eval_derivatives`], and returns whether `x` is a root to
+    /// working precision, i.e. the value is within its error bound of the
+    /// change caused by moving `x` by one unit in the last place, so
+    /// iterating further cannot improve the root
+    fn eval_stopping(&self, x: Complex<T>, out: &mut [Complex<T>]) -> bool {
+        debug_assert!(out.len() >= 2);
+
+        let bound = horner_compensated_derivatives(self.as_slice(), x, out);
+        let noise = bound + T::epsilon() * x.norm() * out[1].norm();
+        out[0].norm() <= noise
+    }
+
     fn one_root_newton(
         &self,
         initial_guess: Option<Complex<T>>,
         epsilon: T,
         max_iter: usize,
-    ) -> Result<Complex<T>, Complex<T>> {
-        let p_diff = self.clone().diff();
+    ) -> Result<(Complex<T>, usize), Complex<T>> {
         let mut x = initial_guess.unwrap_or(self.initial_guess_smallest());
-        for _ in 0..max_iter {
-            let px = self.eval_point(x);
-            if px.norm() <= epsilon {
-                return Ok(x);
+        let mut derivatives = [Complex::zero(); 2];
+        for i in 0..max_iter {
+            let converged = self.eval_stopping(x, &mut derivatives);
+            let [px, pdx] = derivatives;
+            if px.norm() <= epsilon || converged {
+                return Ok((x, i));
             }
-            let pdx = p_diff.eval_point(x);
             x = x - px / pdx;
         }
         Err(x)
@@ -93,24 +147,117 @@ impl<T: Scalar + RealField + Float> Poly<T> {
         initial_guess: Option<Complex<T>>,
         epsilon: T,
         max_iter: usize,
-    ) -> Result<Complex<T>, Complex<T>> {
-        let p_diff = self.clone().diff();
-        let p_diff2 = p_diff.clone().diff();
-
+    ) -> Result<(Complex<T>, usize), Complex<T>> {
         let mut x = initial_guess.unwrap_or(self.initial_guess_smallest());
-        for _ in 0..max_iter {
-            let px = self.eval_point(x);
-            if px.norm() <= epsilon {
-                return Ok(x);
+        let mut derivatives = [Complex::zero(); 3];
+        for i in 0..max_iter {
+            let converged = self.eval_stopping(x, &mut derivatives);
+            let [px, pdx, pddx] = derivatives;
+            if px.norm() <= epsilon || converged {
+                return Ok((x, i));
             }
-            let pdx = p_diff.eval_point(x);
-            let pddx = p_diff2.eval_point(x);
             let two = Complex::from_u32(2).expect("infallible");
             x = x - (px * pdx * two) / (pdx.powu(2) * two - px * pddx);
         }
         Err(x)
     }
 
+    /// Laguerre's method. Every few iterations only a fraction of the step is
+    /// taken, to break limit cycles.
+    ///
+    /// Ref: W. H. Press et al. "Numerical Recipes", section 9.5
+    fn one_root_laguerre(
+        &self,
+        initial_guess: Option<Complex<T>>,
+        epsilon: T,
+        max_iter: usize,
+    ) -> Result<(Complex<T>, usize), Complex<T>> {
+        const CYCLE_BREAK: usize = 10;
+        const FRACTIONS: [f64; 8] = [0.5, 0.25, 0.75, 0.13, 0.38, 0.62, 0.88, 1.0];
+
+        debug_assert!(self.is_normalized());
+        debug_assert!(self.len_raw() >= 2);
+
+        let degree = T::from_usize(self.len_raw() - 1).expect("infallible");
+        let mut x = initial_guess.unwrap_or_else(|| self.initial_guess_smallest());
+        for i in 0..max_iter {
+            let (value, slope, curvature) = horner_derivatives(self.as_slice(), x);
+            if value.norm() <= epsilon {
+                return Ok((x, i));
+            }
+            let mut correction = laguerre_correction(value, slope, curvature, degree);
+            if !correction.is_finite() {
+                // the derivatives vanish too, so jump somewhere else
+                correction =
+                    Complex::from_polar(T::one() + x.norm(), T::from_usize(i).expect("infallible"));
+            }
+            if (i + 1).is_multiple_of(CYCLE_BREAK) {
+                let fraction = FRACTIONS[(i / CYCLE_BREAK) % FRACTIONS.len()];
+                correction = correction.scale(T::from_f64(fraction).expect("infallible"));
+            }
+            x -= correction;
+        }
+        Err(x)
+    }
+
+    /// Divides by the monic `divisor` with synthetic division, running from
+    /// the leading coefficient (forward) if the roots of the divisor are
+    /// smaller than the geometric mean of the moduli of the roots, or from the
+    /// constant term (backward) otherwise. Either direction is stable for
+    /// roots that are small, resp. large, compared to the remaining roots.
+    ///
+    /// Returns the quotient and the largest coefficient of the remainder that
+    /// was discarded.
+    ///
+    /// Ref: G. Peters, J. H. Wilkinson "Practical problems arising in the
+    /// solution of polynomial equations"
+    /// [DOI](https://doi.org/10.1093/imamat/8.1.16)
+    pub(super) fn deflate_monic(&self, divisor: &[Complex<T>]) -> (Self, T) {
+        debug_assert!(self.is_normalized());
+        debug_assert!(divisor.last().is_some_and(One::is_one));
+
+        let degree = self.len_raw() - 1;
+        let m = divisor.len() - 1;
+        assert!(m <= degree, "the divisor must not have a higher degree");
+
+        let mut rem: Vec<_> = self.iter().copied().collect();
+        let mut quotient = vec![Complex::zero(); degree - m + 1];
+        // compare `|d_0|^(n/m) |a_n|` with `|a_0| = |a_n| prod |z_k|` in
+        // logarithms, both directions are equally good for roots of about the
+        // same magnitude, in which case forward keeps the leading coefficient
+        let ratio =
+            T::from_usize(degree).expect("infallible") / T::from_usize(m).expect("infallible");
+        let forward = Float::ln(divisor[0].norm()) * ratio + Float::ln(rem[degree].norm())
+            <= Float::ln(rem[0].norm()) + Float::sqrt(T::epsilon());
+        let remainder = if forward {
+            for k in (0..=degree - m).rev() {
+                quotient[k] = rem[k + m];
+                for (j, d) in divisor.iter().enumerate() {
+                    rem[k + j] -= quotient[k] * d;
+                }
+            }
+            &rem[..m]
+        } else {
+            for k in 0..=degree - m {
+                quotient[k] = rem[k] / divisor[0];
+                for (j, d) in divisor.iter().enumerate() {
+                    rem[k + j] -= quotient[k] * d;
+                }
+            }
+            &rem[degree - m + 1..]
+        };
+        let error = remainder
+            .iter()
+            .map(|&c| c.norm())
+            .fold(T::zero(), Float::max);
+        (Self::from_complex_vec(quotient), error)
+    }
+
+    /// Complex three-stage Jenkins-Traub algorithm (CPOLY).
+    ///
+    /// Ref: M. A. Jenkins, J. F. Traub "A Three-Stage Variable-Shift Iteration
+    /// for Polynomial Zeros and Its Relation to Generalized Rayleigh Iteration"
+    /// [DOI](https://doi.org/10.1007/BF02162150)
     fn one_root_jenkins_traub(
         &mut self,
         epsilon: T,
@@ -119,27 +266,135 @@ impl<T: Scalar + RealField + Float> Poly<T> {
         // TODO: tune these to the size of the polynomial with a lookup table
         const M: usize = 5;
         const L: usize = 100;
+        const MAX_SHIFTS: usize = 20;
+
+        debug_assert!(self.is_normalized());
+        debug_assert!(self.len_raw() >= 2);
 
         self.make_monic();
+        let coeffs = self.as_slice();
+        let degree = self.len_raw() - 1;
+
+        // trivial cases, the iteration below assumes p(0) != 0 and degree >= 2
+        if coeffs[0].is_zero() {
+            return Ok(Complex::zero());
+        }
+        if degree == 1 {
+            return Ok(c_neg(coeffs[0]));
+        }
+
+        let small = T::epsilon() * T::from_u8(10).expect("infallible");
 
-        // stage one
-        let mut h_poly = self.clone().diff();
+        // stage one: no shift, this accentuates the smaller roots
+        let mut h_poly: Vec<_> = self
+            .clone()
+            .diff()
+            .iter()
+            .map(|c| c.unscale(T::from_usize(degree).expect("infallible")))
+            .collect();
         for _ in 0..M {
-            let pz = self.eval_point(Complex::zero());
+            if h_poly[0].norm() > small * coeffs[1].norm() {
+                let correction = c_neg(coeffs[0] / h_poly[0]);
+                for j in 0..degree - 1 {
+                    h_poly[j] = correction * h_poly[j + 1] + coeffs[j + 1];
+                }
+                h_poly[degree - 1] = coeffs[degree];
+            } else {
+                // h(0) is negligible, so just divide by z
+                h_poly.rotate_left(1);
+                h_poly[degree - 1] = Complex::zero();
+            }
+        }
 
-            // TODO: too many clones
-            let hz = h_poly.clone().eval_point(Complex::zero());
-            h_poly = h_poly - self.clone().scaled(hz / pz);
+        // stage two: fixed shift on a circle of radius equal to the Cauchy
+        // lower bound, rotating the shift by 94 degrees on every failure
+        let bound = self.cauchy_lower_bound();
+        let rotation = Complex::from_polar(
+            T::one(),
+            Float::to_radians(T::from_u8(94).expect("infallible")),
+        );
+        let mut direction = Complex::from_polar(T::one(), T::frac_pi_4());
+        let mut last = Complex::zero();
+        for _ in 0..MAX_SHIFTS {
+            direction *= rotation;
+            let shift = direction.scale(bound);
+            match Self::jenkins_traub_fixed_shift(coeffs, &mut h_poly, shift, L, epsilon, max_iter)
+            {
+                Ok(z) => return Ok(z),
+                Err(z) => last = z,
+            }
+        }
+        Err(last)
+    }
 
-            // TODO: linear division can be done with synthetic division
-            h_poly = h_poly / poly![T::zero(), T::one()];
+    /// Stage two of [`Poly::one_root_jenkins_traub`], moves on to stage three
+    /// when the fixed shift iteration appears to be converging.
+    fn jenkins_traub_fixed_shift(
+        coeffs: &[Complex<T>],
+        h_poly: &mut Vec<Complex<T>>,
+        shift: Complex<T>,
+        max_iter_fixed: usize,
+        epsilon: T,
+        max_iter: usize,
+    ) -> Result<Complex<T>, Complex<T>> {
+        let half = Float::recip(T::from_u8(2).expect("infallible"));
+        let (p_quot, px) = horner_div(coeffs, shift);
+        let (mut correction, mut h_is_zero) = jenkins_traub_correction(h_poly, shift, px);
+        let mut passed = false;
+        for j in 0..max_iter_fixed {
+            let old_correction = correction;
+            jenkins_traub_next_h(h_poly, &p_quot, shift, correction, h_is_zero);
+            (correction, h_is_zero) = jenkins_traub_correction(h_poly, shift, px);
+            let guess = shift + correction;
+
+            // stage three is only started after two consecutive successful
+            // tests, to avoid starting it too early
+            if !h_is_zero && j != 0 && (correction - old_correction).norm() < guess.norm() * half {
+                if passed {
+                    let h_saved = h_poly.clone();
+                    if let Ok(z) =
+                        Self::jenkins_traub_variable_shift(coeffs, h_poly, guess, epsilon, max_iter)
+                    {
+                        return Ok(z);
+                    }
+
+                    // stage three failed, resume stage two from where it was
+                    *h_poly = h_saved;
+                    passed = false;
+                    (correction, h_is_zero) = jenkins_traub_correction(h_poly, shift, px);
+                } else {
+                    passed = true;
+                }
+            } else {
+                passed = false;
+            }
         }
 
-        // stage two
-        todo!();
+        // attempt stage three with the final h polynomial from stage two
+        Self::jenkins_traub_variable_shift(coeffs, h_poly, shift + correction, epsilon, max_iter)
+    }
 
-        // stage three
-        todo!()
+    /// Stage three of [`Poly::one_root_jenkins_traub`]
+    fn jenkins_traub_variable_shift(
+        coeffs: &[Complex<T>],
+        h_poly: &mut [Complex<T>],
+        mut x: Complex<T>,
+        epsilon: T,
+        max_iter: usize,
+    ) -> Result<Complex<T>, Complex<T>> {
+        for _ in 0..max_iter {
+            let (p_quot, px) = horner_div(coeffs, x);
+            if px.norm() <= epsilon {
+                return Ok(x);
+            }
+            let (correction, h_is_zero) = jenkins_traub_correction(h_poly, x, px);
+            jenkins_traub_next_h(h_poly, &p_quot, x, correction, h_is_zero);
+            let (correction, h_is_zero) = jenkins_traub_correction(h_poly, x, px);
+            if !h_is_zero {
+                x += correction;
+            }
+        }
+        Err(x)
     }
 
     fn roots_francis_qr(&self, epsilon: T, max_iter: usize) -> Result<Vec<Complex<T>>, Error> {
@@ -151,7 +406,6 @@ impl<T: Scalar + RealField + Float> Poly<T> {
         // TODO: tune max_iter_per_deflation to input
 
         let mut comp = self.companion();
-        println!("{comp}");
 
         // rotating the matrix 180 degrees. This is equivalent to using similarity
         // transforms so it does not move the eigenvalues. But NumPy does it
@@ -168,6 +422,641 @@ impl<T: Scalar + RealField + Float> Poly<T> {
         //       read the papers by Karen Braman, Ralph Byers and Roy Mathias
         __util::linalg::eigen_francis_shift(comp.as_view_mut(), epsilon, max_iter, max_iter)
     }
+
+    fn roots_multishift_qr(&self, epsilon: T, max_iter: usize) -> Result<Vec<Complex<T>>, Error> {
+        debug_assert!(self.degree_raw() >= 1);
+
+        // the companion matrix is already upper Hessenberg, so it is not
+        // rotated like in `roots_francis_qr`, which would undo that
+        let mut comp = self.companion();
+        __util::linalg::eigen_multishift_qr(comp.as_view_mut(), epsilon, max_iter, max_iter)
+    }
+
+    fn roots_structured_qr(&self, epsilon: T, max_iter: usize) -> Result<Vec<Complex<T>>, Error> {
+        debug_assert!(self.len_raw() >= 2);
+
+        let mut monic = self.clone();
+        monic.make_monic();
+        let coeffs = &monic.as_slice()[..monic.len_raw() - 1];
+        __util::companion_qr::roots_companion_qr(coeffs, epsilon, max_iter)
+    }
+
+    /// Aberth-Ehrlich method starting from the given approximations, one for
+    /// each root, and updating them in place as in the Gauss-Seidel style
+    /// iteration used by `MPSolve`.
+    ///
+    /// A root is considered converged when `|p(z)|` is below `epsilon`, as in
+    /// the other algorithms, or below the rounding error of evaluating the
+    /// polynomial, after which it is not refined any further.
+    /// Each root is paired with the number of iterations it took to converge.
+    ///
+    /// Ref: D. A. Bini "Numerical computation of polynomial zeros by means of
+    /// Aberth's method" [DOI](https://doi.org/10.1007/BF02207694)
+    fn roots_aberth(
+        &self,
+        mut roots: Vec<Complex<T>>,
+        epsilon: T,
+        max_iter: usize,
+    ) -> Result<Vec<(Complex<T>, usize)>, Error> {
+        debug_assert!(self.is_normalized());
+        debug_assert!(self.len_raw() >= 2);
+
+        // scaling the coefficients does not move the roots, but it prevents
+        // overflows when evaluating polynomials with large coefficients
+        let max_coeff = self.iter().map(|&c| c.norm()).fold(T::zero(), Float::max);
+        let coeffs: Vec<_> = self.iter().map(|c| c.unscale(max_coeff)).collect();
+        let degree = self.len_raw() - 1;
+        let noise = T::epsilon() * T::from_usize(2 * degree).expect("infallible");
+
+        debug_assert_eq!(roots.len(), degree);
+        let mut iterations = vec![None; degree];
+        for iter in 0..max_iter {
+            for i in 0..degree {
+                if iterations[i].is_some() {
+                    continue;
+                }
+                let z = roots[i];
+                let (ratio, residual, bound) = newton_ratio(&coeffs, z);
+                if residual <= scaled_tolerance(epsilon, max_coeff, z, degree)
+                    || residual <= noise * bound
+                {
+                    iterations[i] = Some(iter);
+                    continue;
+                }
+                let repulsion = roots
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .fold(Complex::<T>::zero(), |acc, (_, zj)| acc + (z - zj).inv());
+                let correction = ratio / (Complex::<T>::one() - ratio * repulsion);
+                if correction.is_finite() {
+                    roots[i] = z - correction;
+                } else {
+                    // the derivative vanishes, so nudge the approximation away
+                    roots[i] = z + z.scale(Float::sqrt(T::epsilon())) + T::epsilon();
+                }
+            }
+            if iterations.iter().all(Option::is_some) {
+                return Ok(roots
+                    .into_iter()
+                    .zip(iterations.into_iter().flatten())
+                    .collect());
+            }
+        }
+        Err(Error::max_iter_inner())
+    }
+
+    /// Durand-Kerner (Weierstrass) method, with the same stopping criterion
+    /// as [`Poly::roots_aberth`].
+    ///
+    /// Ref: E. Durand "Solutions numériques des équations algébriques",
+    /// I. O. Kerner "Ein Gesamtschrittverfahren zur Berechnung der Nullstellen
+    /// von Polynomen" [DOI](https://doi.org/10.1007/BF02162161)
+    fn roots_durand_kerner(
+        &self,
+        mut roots: Vec<Complex<T>>,
+        epsilon: T,
+        max_iter: usize,
+    ) -> Result<Vec<(Complex<T>, usize)>, Error> {
+        debug_assert!(self.is_normalized());
+        debug_assert!(self.len_raw() >= 2);
+
+        let max_coeff = self.iter().map(|&c| c.norm()).fold(T::zero(), Float::max);
+        let coeffs: Vec<_> = self.iter().map(|c| c.unscale(max_coeff)).collect();
+        let degree = self.len_raw() - 1;
+        let noise = T::epsilon() * T::from_usize(2 * degree).expect("infallible");
+
+        debug_assert_eq!(roots.len(), degree);
+        let mut iterations = vec![None; degree];
+        for iter in 0..max_iter {
+            for i in 0..degree {
+                if iterations[i].is_some() {
+                    continue;
+                }
+                let z = roots[i];
+                let (correction, residual, bound) = durand_kerner_correction(&coeffs, &roots, i);
+                if residual <= scaled_tolerance(epsilon, max_coeff, z, degree)
+                    || residual <= noise * bound
+                {
+                    iterations[i] = Some(iter);
+                    continue;
+                }
+                if correction.is_finite() {
+                    roots[i] = z - correction;
+                } else {
+                    // two approximations coincide, so nudge one of them away
+                    roots[i] = z + z.scale(Float::sqrt(T::epsilon())) + T::epsilon();
+                }
+            }
+            if iterations.iter().all(Option::is_some) {
+                return Ok(roots
+                    .into_iter()
+                    .zip(iterations.into_iter().flatten())
+                    .collect());
+            }
+        }
+        Err(Error::max_iter_inner())
+    }
+
+    /// Roots of a linear or quadratic polynomial, the quadratic formula picks
+    /// the sign that avoids cancellation and gets the other root from the
+    /// product of the roots
+    pub(crate) fn roots_closed_form(&self) -> Vec<Complex<T>> {
+        debug_assert!(self.is_normalized());
+        debug_assert!((2..=3).contains(&self.len_raw()));
+
+        let two = T::one() + T::one();
+        match *self.as_slice() {
+            [c0, c1] => vec![-c0 / c1],
+            [c0, c1, c2] => {
+                let mut sqrt_disc = (c1 * c1 - c2 * c0.scale(two + two)).sqrt();
+                if (c1.conj() * sqrt_disc).re < T::zero() {
+                    sqrt_disc = -sqrt_disc;
+                }
+                let q = -(c1 + sqrt_disc).unscale(two);
+                if q.is_zero() {
+                    return vec![Complex::zero(); 2];
+                }
+                vec![q / c2, c0 / q]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Runs one of the [`OneRootAlgorithms`], also returning the number of
+    /// iterations if the algorithm keeps track of it.
+    pub(crate) fn one_root(
+        &self,
+        algorithm: OneRootAlgorithms,
+        initial_guess: Option<Complex<T>>,
+        epsilon: T,
+        max_iter: usize,
+    ) -> Result<(Complex<T>, Option<usize>), Complex<T>> {
+        match algorithm {
+            OneRootAlgorithms::Newton => self
+                .one_root_newton(initial_guess, epsilon, max_iter)
+                .map(|(z, iterations)| (z, Some(iterations))),
+            OneRootAlgorithms::Halley => self
+                .one_root_halley(initial_guess, epsilon, max_iter)
+                .map(|(z, iterations)| (z, Some(iterations))),
+            OneRootAlgorithms::JenkinsTraub => self
+                .clone()
+                .one_root_jenkins_traub(epsilon, max_iter)
+                .map(|z| (z, None)),
+            OneRootAlgorithms::Laguerre => self
+                .one_root_laguerre(initial_guess, epsilon, max_iter)
+                .map(|(z, iterations)| (z, Some(iterations))),
+        }
+    }
+
+    /// Runs one of the [`AllRootsAlgorithms`], pairing each root with the
+    /// number of iterations it took if the algorithm keeps track of it.
+    ///
+    /// The simultaneous methods start from `initial_guesses`, completed with
+    /// the guesses from the Newton polygon if there are fewer than the degree.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn all_roots(
+        &self,
+        algorithm: AllRootsAlgorithms,
+        initial_guesses: &[Complex<T>],
+        epsilon: T,
+        max_iter: usize,
+    ) -> Result<Vec<(Complex<T>, Option<usize>)>, Error> {
+        let guesses = || {
+            let degree = self.len_raw() - 1;
+            let mut guesses = initial_guesses[..initial_guesses.len().min(degree)].to_vec();
+            guesses.extend(
+                self.initial_guesses_newton_polygon()
+                    .into_iter()
+                    .skip(guesses.len()),
+            );
+            guesses
+        };
+        let untracked = |roots: Vec<_>| roots.into_iter().map(|z| (z, None)).collect();
+        let tracked = |roots: Vec<_>| roots.into_iter().map(|(z, i)| (z, Some(i))).collect();
+        match algorithm {
+            AllRootsAlgorithms::Schur | AllRootsAlgorithms::FrancisQR => {
+                self.roots_francis_qr(epsilon, max_iter).map(untracked)
+            }
+            AllRootsAlgorithms::MultishiftQR => {
+                self.roots_multishift_qr(epsilon, max_iter).map(untracked)
+            }
+            AllRootsAlgorithms::Aberth => {
+                self.roots_aberth(guesses(), epsilon, max_iter).map(tracked)
+            }
+            AllRootsAlgorithms::DurandKerner => self
+                .roots_durand_kerner(guesses(), epsilon, max_iter)
+                .map(tracked),
+            AllRootsAlgorithms::StructuredQR => {
+                self.roots_structured_qr(epsilon, max_iter).map(untracked)
+            }
+        }
+    }
+}
+
+impl<T: Scalar + RealField + Float> Poly<T> {
+    /// The unique positive root of `|a_n|z^n + ... + |a_1|z - |a_0|`, which
+    /// is a lower bound on the moduli of the roots.
+    fn cauchy_lower_bound(&self) -> T {
+        debug_assert!(self.is_normalized());
+        debug_assert!(self.len_raw() >= 2);
+
+        let moduli: Vec<T> = self.iter().map(|&c| c.norm()).collect();
+        cauchy_root(&moduli)
+    }
+
+    /// Initial guesses for simultaneous root finders, evenly spaced on
+    /// circles with radii given by the slopes of the Newton polygon, i.e. the
+    /// upper convex hull of the points `(k, log|a_k|)`.
+    ///
+    /// Ref: D. A. Bini "Numerical computation of polynomial zeros by means of
+    /// Aberth's method" [DOI](https://doi.org/10.1007/BF02207694)
+    fn initial_guesses_newton_polygon(&self) -> Vec<Complex<T>> {
+        debug_assert!(self.is_normalized());
+        debug_assert!(self.len_raw() >= 2);
+
+        let degree = self.len_raw() - 1;
+        let points: Vec<(usize, T)> = self
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(k, &c)| (k, Float::ln(c.norm())))
+            .collect();
+
+        // monotone chain, keeping only the points that make a right turn
+        let mut hull: Vec<(usize, T)> = Vec::with_capacity(points.len());
+        for point in points {
+            while let [.., (k1, y1), (k2, y2)] = hull[..] {
+                let dk1 = T::from_usize(k2 - k1).expect("infallible");
+                let dk2 = T::from_usize(point.0 - k2).expect("infallible");
+                if (y2 - y1) * dk2 > (point.1 - y2) * dk1 {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push(point);
+        }
+
+        // zero roots are placed on the innermost circle together with the
+        // smallest non-zero roots
+        hull[0].0 = 0;
+
+        // a small offset avoids placing the guesses symmetrically with
+        // respect to the real axis
+        let sigma = T::from_f64(0.7).expect("infallible");
+        let tau = T::two_pi();
+        let n = T::from_usize(degree).expect("infallible");
+        let mut guesses = Vec::with_capacity(degree);
+        for (i, window) in hull.windows(2).enumerate() {
+            let [(k1, y1), (k2, y2)] = window else {
+                unreachable!()
+            };
+            let count = k2 - k1;
+            let m = T::from_usize(count).expect("infallible");
+            let radius = Float::exp((*y1 - *y2) / m);
+            let offset = tau * T::from_usize(i).expect("infallible") / n + sigma;
+            guesses.extend((0..count).map(|j| {
+                let angle = tau * T::from_usize(j).expect("infallible") / m + offset;
+                Complex::from_polar(radius, angle)
+            }));
+        }
+        guesses
+    }
+
+    /// The cofactors `u = p / gcd(p, p')` and `v = p' / gcd(p, p')` of the
+    /// approximate GCD, such that `u` is square-free and `v(z) = m u'(z)` at
+    /// every root `z` of multiplicity `m`.
+    ///
+    /// The degree of `u` is the smallest `l` for which `p' u - p v = 0` has a
+    /// numerical solution, i.e. the Sylvester-like matrix `[C(p'), -C(p)]` has
+    /// a singular value below `epsilon`, which is found by bisection since
+    /// every larger `l` has a solution too.
+    ///
+    /// Ref: Z. Zeng "Computing multiple roots of inexact polynomials"
+    /// [DOI](https://doi.org/10.1090/S0025-5718-04-01692-8)
+    pub(crate) fn gcd_cofactors(&self, epsilon: T) -> (Self, Self) {
+        debug_assert!(self.is_normalized());
+        debug_assert!(self.len_raw() >= 2);
+
+        let degree = self.len_raw() - 1;
+        let l2_norm = |p: &Self| {
+            Float::sqrt(
+                p.iter()
+                    .map(Complex::norm_sqr)
+                    .fold(T::zero(), |a, b| a + b),
+            )
+        };
+        let p_diff = self.clone().diff();
+        let (p_norm, p_diff_norm) = (l2_norm(self), l2_norm(&p_diff));
+        let p_scaled: Vec<_> = self.iter().map(|c| c.unscale(p_norm)).collect();
+        let p_diff_scaled: Vec<_> = p_diff.iter().map(|c| c.unscale(p_diff_norm)).collect();
+
+        let cofactors = |l: usize| {
+            let mut sylvester = na::DMatrix::zeros(degree + l, 2 * l + 1);
+            sylvester
+                .columns_mut(0, l + 1)
+                .copy_from(&__util::linalg::convolution_matrix(&p_diff_scaled, l + 1));
+            sylvester
+                .columns_mut(l + 1, l)
+                .copy_from(&-__util::linalg::convolution_matrix(&p_scaled, l));
+            let svd = sylvester.svd(false, true);
+            // singular values are sorted in descending order
+            if svd.singular_values[2 * l] > epsilon {
+                return None;
+            }
+            let v_t = svd.v_t.expect("right singular vectors were requested");
+            let null: Vec<_> = v_t.row(2 * l).iter().map(Complex::conj).collect();
+            Some((null[..=l].to_vec(), null[l + 1..].to_vec()))
+        };
+
+        // `u = p` and `v = p'` when all roots are simple
+        let mut best = (p_scaled.clone(), p_diff_scaled.clone());
+        let (mut lo, mut hi) = (1, degree);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if let Some(found) = cofactors(mid) {
+                best = found;
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+
+        // undo the scaling, `p' u = p v` holds for the unscaled polynomials
+        let (u, v) = best;
+        let v_scale = p_diff_norm / p_norm;
+        (
+            Self::from_complex_vec(u),
+            Self::from_complex_vec(v.iter().map(|c| c.scale(v_scale)).collect()),
+        )
+    }
+
+    /// Refines distinct roots with the given multiplicities by Gauss-Newton
+    /// iteration on the coefficients of `(z - z_1)^m_1 ... (z - z_l)^m_l`,
+    /// which is well conditioned, unlike the roots of the polynomial itself.
+    ///
+    /// Stops when the weighted residual no longer decreases.
+    ///
+    /// Ref: Z. Zeng "Computing multiple roots of inexact polynomials"
+    /// [DOI](https://doi.org/10.1090/S0025-5718-04-01692-8)
+    fn refine_multiple_roots(&self, roots: &mut [(Complex<T>, usize)], max_iter: usize) {
+        debug_assert!(self.is_normalized());
+
+        let mut monic = self.clone();
+        monic.make_monic();
+        let degree = monic.len_raw() - 1;
+        let weights: Vec<T> = monic
+            .iter()
+            .take(degree)
+            .map(|c| Float::recip(Float::max(c.norm(), T::one())))
+            .collect();
+        let residual = |roots: &[(Complex<T>, usize)]| {
+            let expanded = expand_roots_with_multiplicity(roots);
+            na::DVector::from_iterator(
+                degree,
+                (0..degree).map(|k| (expanded[k] - monic.0[k]).scale(weights[k])),
+            )
+        };
+
+        let mut res = residual(roots);
+        for _ in 0..max_iter {
+            // the derivative with respect to `z_j` is `-m_j q(z) / (z - z_j)`
+            let mut jacobian = na::DMatrix::zeros(degree, roots.len());
+            for j in 0..roots.len() {
+                let multiplicity = roots[j].1;
+                roots[j].1 -= 1;
+                let column = expand_roots_with_multiplicity(roots);
+                roots[j].1 += 1;
+                let m = T::from_usize(multiplicity).expect("infallible");
+                for k in 0..degree {
+                    jacobian[(k, j)] = -column[k].scale(m * weights[k]);
+                }
+            }
+
+            let Ok(step) = jacobian.svd(true, true).solve(&res, T::epsilon()) else {
+                break;
+            };
+            let previous = roots.to_vec();
+            for (root, delta) in roots.iter_mut().zip(step.iter()) {
+                root.0 -= delta;
+            }
+            let next_res = residual(roots);
+            if next_res.norm() >= res.norm() {
+                roots.copy_from_slice(&previous);
+                break;
+            }
+            res = next_res;
+        }
+    }
+}
+
+/// Coefficients of the monic polynomial `(z - z_1)^m_1 ... (z - z_l)^m_l`,
+/// lowest degree first.
+fn expand_roots_with_multiplicity<T: Scalar + RealField + Float>(
+    roots: &[(Complex<T>, usize)],
+) -> Vec<Complex<T>> {
+    let mut coeffs = vec![Complex::one()];
+    for &(root, multiplicity) in roots {
+        for _ in 0..multiplicity {
+            coeffs.push(Complex::zero());
+            for k in (1..coeffs.len()).rev() {
+                coeffs[k] = coeffs[k - 1] - root * coeffs[k];
+            }
+            coeffs[0] = -root * coeffs[0];
+        }
+    }
+    coeffs
+}
+
+/// Divide by `z - s` using Horner's scheme, returning the quotient and the
+/// remainder, which is the value of the polynomial at `s`.
+fn horner_div<T: Scalar + RealField + Float>(
+    coeffs: &[Complex<T>],
+    s: Complex<T>,
+) -> (Vec<Complex<T>>, Complex<T>) {
+    debug_assert!(!coeffs.is_empty());
+
+    let n = coeffs.len() - 1;
+    let mut quotient = vec![Complex::zero(); n];
+    let mut acc = coeffs[n];
+    for i in (0..n).rev() {
+        quotient[i] = acc;
+        acc = acc * s + coeffs[i];
+    }
+    (quotient, acc)
+}
+
+/// Computes the Newton correction `p(z)/p'(z)`, the residual `|p(z)|` and the
+/// bound `|a_n||z|^n + ... + |a_0|` used to estimate the rounding error of the
+/// residual.
+///
+/// Outside of the unit circle, the reversed polynomial is evaluated at `1/z`
+/// instead, to avoid overflows, in which case the residual and the bound are
+/// both scaled by `|z|^-n`.
+fn newton_ratio<T: Scalar + RealField + Float>(
+    coeffs: &[Complex<T>],
+    z: Complex<T>,
+) -> (Complex<T>, T, T) {
+    debug_assert!(coeffs.len() >= 2);
+
+    if z.norm() <= T::one() {
+        let (pz, pdz, bound) = horner_with_bound(coeffs.iter().rev(), z);
+        return (pz / pdz, pz.norm(), bound);
+    }
+
+    // p(z) = z^n q(1/z), so p(z)/p'(z) = z / (n - q'(w)/q(w) w) with w = 1/z
+    let w = z.inv();
+    let n = T::from_usize(coeffs.len() - 1).expect("infallible");
+    let (qw, qdw, bound) = horner_with_bound(coeffs.iter(), w);
+    (z / (-(qdw / qw) * w + n), qw.norm(), bound)
+}
+
+/// The absolute residual threshold `epsilon` on `|p(z)|`, in the units of
+/// the residual returned by [`newton_ratio`] and [`weierstrass_factors`] for
+/// the coefficients divided by `max_coeff`
+fn scaled_tolerance<T: Scalar + RealField + Float>(
+    epsilon: T,
+    max_coeff: T,
+    z: Complex<T>,
+    degree: usize,
+) -> T {
+    let tol = epsilon / max_coeff;
+    if z.norm() <= T::one() {
+        return tol;
+    }
+    // the residual is `|p(z)| / |z|^n` outside the unit disk
+    let n = T::from_usize(degree).expect("infallible");
+    tol * Float::powf(Float::recip(z.norm()), n)
+}
+
+/// The value, first and second derivative with Horner's method
+pub(super) fn horner_derivatives<T: Scalar + Float>(
+    coeffs: &[Complex<T>],
+    x: Complex<T>,
+) -> (Complex<T>, Complex<T>, Complex<T>) {
+    let (px, pdx, half_pddx) = coeffs.iter().rev().fold(
+        (
+            Complex::<T>::zero(),
+            Complex::<T>::zero(),
+            Complex::<T>::zero(),
+        ),
+        |(px, pdx, half_pddx), &c| (px * x + c, pdx * x + px, half_pddx * x + pdx),
+    );
+    (px, pdx, half_pddx + half_pddx)
+}
+
+/// The correction of Laguerre's method `n / (G +- sqrt((n - 1)(n H - G^2)))`
+/// with `G = p'/p` and `H = G^2 - p''/p`, where the sign is chosen to make
+/// the correction as small as possible.
+pub(super) fn laguerre_correction<T: Scalar + Float>(
+    value: Complex<T>,
+    slope: Complex<T>,
+    curvature: Complex<T>,
+    degree: T,
+) -> Complex<T> {
+    let g = slope / value;
+    let g_sqr = g * g;
+    let h = g_sqr - curvature / value;
+    let sqrt = ((h.scale(degree) - g_sqr).scale(degree - T::one())).sqrt();
+    let denominator = if (g + sqrt).norm_sqr() >= (g - sqrt).norm_sqr() {
+        g + sqrt
+    } else {
+        g - sqrt
+    };
+    Complex::from(degree) / denominator
+}
+
+/// Computes the Weierstrass correction `p(z_i) / (a_n prod_{j != i} (z_i - z_j))`
+/// for the `i`-th root, the residual and the bound like in [`newton_ratio`].
+fn durand_kerner_correction<T: Scalar + RealField + Float>(
+    coeffs: &[Complex<T>],
+    roots: &[Complex<T>],
+    i: usize,
+) -> (Complex<T>, T, T) {
+    let (value, weight, bound) = weierstrass_factors(coeffs, roots, i);
+    (value * weight, value.norm(), bound)
+}
+
+/// Splits the Weierstrass correction of the `i`-th root into the value of
+/// the polynomial and the weight `1 / (a_n prod_{j != i} (z_i - z_j))`, with
+/// the same scaling as in [`newton_ratio`], together with the bound.
+fn weierstrass_factors<T: Scalar + RealField + Float>(
+    coeffs: &[Complex<T>],
+    roots: &[Complex<T>],
+    i: usize,
+) -> (Complex<T>, Complex<T>, T) {
+    debug_assert!(coeffs.len() >= 2);
+
+    let z = roots[i];
+    let lead = coeffs[coeffs.len() - 1];
+    let others = roots
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| *j != i)
+        .map(|(_, zj)| z - zj);
+
+    // the factors are divided one at a time to avoid overflows in the product
+    if z.norm() <= T::one() {
+        let (pz, _, bound) = horner_with_bound(coeffs.iter().rev(), z);
+        let weight = others.fold(lead.inv(), |acc, d| acc / d);
+        return (pz, weight, bound);
+    }
+
+    // p(z) = z^n q(1/z), one factor of z is paired with each difference
+    let (qw, _, bound) = horner_with_bound(coeffs.iter(), z.inv());
+    let weight = others.fold(z / lead, |acc, d| acc * (z / d));
+    (qw, weight, bound)
+}
+
+/// Evaluates a polynomial and its derivative with Horner's scheme, given the
+/// coefficients from the highest degree, together with the bound
+/// `|a_n||x|^n + ... + |a_0|` on the magnitude of the terms.
+pub(super) fn horner_with_bound<'a, T: Scalar + RealField + Float>(
+    coeffs: impl Iterator<Item = &'a Complex<T>>,
+    x: Complex<T>,
+) -> (Complex<T>, Complex<T>, T) {
+    let x_abs = x.norm();
+    coeffs.fold(
+        (Complex::zero(), Complex::zero(), T::zero()),
+        |(px, pdx, bound), &c| (px * x + c, pdx * x + px, bound * x_abs + c.norm()),
+    )
+}
+
+/// Computes the Jenkins-Traub correction `t = -p(s)/h(s)` and whether `h(s)`
+/// is negligible, in which case the correction is zero.
+fn jenkins_traub_correction<T: Scalar + RealField + Float>(
+    h: &[Complex<T>],
+    s: Complex<T>,
+    ps: Complex<T>,
+) -> (Complex<T>, bool) {
+    let (_, hs) = horner_div(h, s);
+    let small = T::epsilon() * T::from_u8(10).expect("infallible");
+    if hs.norm() <= small * h[0].norm() {
+        return (Complex::zero(), true);
+    }
+    (c_neg(ps / hs), false)
+}
+
+/// Computes the next (scaled) h polynomial, `h <- (h - h(s)/p(s) p) / (z - s)`
+/// given the quotient of `p / (z - s)`.
+fn jenkins_traub_next_h<T: Scalar + RealField + Float>(
+    h: &mut [Complex<T>],
+    qp: &[Complex<T>],
+    s: Complex<T>,
+    t: Complex<T>,
+    h_is_zero: bool,
+) {
+    let n = h.len();
+    let (qh, _) = horner_div(h, s);
+    if h_is_zero {
+        h[..n - 1].copy_from_slice(&qh);
+        h[n - 1] = Complex::zero();
+    } else {
+        for j in 0..n - 1 {
+            h[j] = qp[j] + t * qh[j];
+        }
+        h[n - 1] = qp[n - 1];
+    }
 }
 
 impl<T: Scalar + Float + RealField> Poly<T> {
@@ -181,8 +1070,16 @@ impl<T: Scalar + Float + RealField> Poly<T> {
     /// `Err` result contains the roots it was able to find, even if they are
     /// fewer than requested.
     ///
+    /// If `initial_guess` is `None`, the `i`-th root starts from the `i`-th
+    /// of [`Poly::initial_root_guesses`].
+    ///
     /// Use [`Poly::try_n_roots_algo`] to specify which algorithm to use, if
     /// you already know which one will perform best.
+    ///
+    /// Newton's and Halley's methods also stop at a root to working
+    /// precision, where `|p(x)|` is below the error bound of
+    /// [`Poly::eval_compensated`] plus `eps |x| |p'(x)|`, even if it is still
+    /// above `epsilon`.
     pub fn try_n_roots(
         &self,
         n: usize,
@@ -203,28 +1100,69 @@ impl<T: Scalar + Float + RealField> Poly<T> {
 
         let algorithm = algorithm.unwrap_or(OneRootAlgorithms::Newton);
 
+        // without a guess, each root starts from the next circle of the
+        // Newton polygon, since the smaller roots are removed first
+        let guesses = if initial_guess.is_some() {
+            vec![]
+        } else {
+            self.initial_root_guesses()
+        };
+
         let mut roots = vec![];
         let mut this = self.clone();
         for i in 0..n {
-            let r = match algorithm {
-                OneRootAlgorithms::Newton => this
-                    .clone()
-                    .one_root_newton(initial_guess, epsilon, max_iter)
-                    .map_err(|_| roots.clone())?,
-                OneRootAlgorithms::Halley => this
-                    .clone()
-                    .one_root_halley(initial_guess, epsilon, max_iter)
-                    .map_err(|_| roots.clone())?,
-                OneRootAlgorithms::JenkinsTraub => unimplemented!(),
-            };
-            roots.push(r.clone());
+            let guess = initial_guess.or_else(|| guesses.get(i).copied());
+            let (r, _) = this
+                .one_root(algorithm, guess, epsilon, max_iter)
+                .map_err(|_| roots.clone())?;
+            roots.push(r);
             if i < (n - 1) {
-                this = this / Poly::from_roots(&[r.clone()]);
+                this = this.deflate_linear(r).0;
             }
         }
         Ok(roots)
     }
 
+    /// One starting point per root for iterative root finders, evenly
+    /// spaced on circles with radii given by the slopes of the Newton
+    /// polygon, i.e. the upper convex hull of the points `(k, log|a_k|)`.
+    ///
+    /// The slopes are good estimates of the moduli of the roots unless they
+    /// are clustered, and the guesses are ordered from the innermost circle
+    /// outwards. They suit simultaneous methods as well as finding the roots
+    /// one at a time, smallest first, with [`Poly::try_n_roots`].
+    ///
+    /// Ref: D. A. Bini "Numerical computation of polynomial zeros by means of
+    /// Aberth's method" [DOI](https://doi.org/10.1007/BF02207694)
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::Poly;
+    /// use num::Complex;
+    ///
+    /// let p = Poly::from_roots(&[Complex::new(1E-3, 0.0), Complex::new(1E3, 0.0)]);
+    /// let guesses = p.initial_root_guesses();
+    /// assert_eq!(guesses.len(), 2);
+    /// assert!(guesses[0].norm() < 1E-2);
+    /// assert!(guesses[1].norm() > 1E2);
+    /// ```
+    #[must_use]
+    pub fn initial_root_guesses(&self) -> Vec<Complex<T>> {
+        debug_assert!(self.is_normalized());
+
+        if self.len_raw() < 2 {
+            return vec![];
+        }
+        self.initial_guesses_newton_polygon()
+    }
+
+    /// Find all the roots of the polynomial.
+    ///
+    /// This is a shorthand for running a [`RootFinder`] with the given
+    /// settings, which also reports how each root was found.
+    ///
+    /// `Err` result contains the roots it was able to find, even if they are
+    /// fewer than the degree.
     pub fn try_roots(
         &self,
         epsilon: T,
@@ -234,72 +1172,211 @@ impl<T: Scalar + Float + RealField> Poly<T> {
         algorithm: Option<AllRootsAlgorithms>,
         recovery_algorithm: Option<OneRootAlgorithms>,
     ) -> Result<Vec<Complex<T>>, Vec<Complex<T>>> {
+        let mut finder = RootFinder::new()
+            .epsilon(epsilon)
+            .max_iter(max_iter)
+            .max_tries(max_tries);
+        if let Some(max_recovery_iter) = max_recovery_iter {
+            finder = finder.max_recovery_iter(max_recovery_iter);
+        }
+        if let Some(algorithm) = algorithm {
+            finder = finder.algorithm(algorithm);
+        }
+        if let Some(recovery_algorithm) = recovery_algorithm {
+            finder = finder.recovery_algorithm(recovery_algorithm);
+        }
+        finder
+            .run(self)
+            .map(RootReport::into_roots)
+            .map_err(RootReport::into_roots)
+    }
+
+    /// Find the distinct roots of the polynomial, together with their
+    /// multiplicities.
+    ///
+    /// The multiplicity structure is determined by an approximate GCD of the
+    /// polynomial and its derivative, where `epsilon` is the relative
+    /// tolerance of the GCD, i.e. roughly how perturbed the coefficients may be.
+    /// The roots are then refined with this structure imposed, so they are
+    /// accurate to near machine precision even for high multiplicities,
+    /// unlike the clusters returned by [`Poly::try_roots`].
+    ///
+    /// # Errors
+    /// Fails if the roots of the square-free part do not converge within
+    /// `max_iter` iterations, or if the multiplicities are inconsistent with
+    /// the degree, which means that `epsilon` is too small for the
+    /// perturbation in the coefficients.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::Poly;
+    /// use num::Complex;
+    ///
+    /// let p = Poly::from_roots(&[Complex::new(1.0, 0.0); 5]);
+    /// let roots = p.roots_with_multiplicity(1E-10, 100).unwrap();
+    /// assert_eq!(roots.len(), 1);
+    /// assert_eq!(roots[0].1, 5);
+    /// assert!((roots[0].0 - 1.0).norm() < 1E-14);
+    /// ```
+    pub fn roots_with_multiplicity(
+        &self,
+        epsilon: T,
+        max_iter: usize,
+    ) -> Result<Vec<(Complex<T>, usize)>, Error> {
         debug_assert!(self.is_normalized());
 
         if self.len_raw() < 2 {
             return Ok(vec![]);
         }
 
-        let max_recovery_iter = max_recovery_iter.unwrap_or(max_iter);
-        let algorithm = algorithm.unwrap_or(AllRootsAlgorithms::FrancisQR);
-        let recovery_algorithm = recovery_algorithm.unwrap_or(OneRootAlgorithms::Newton);
+        let (u, v) = self.gcd_cofactors(epsilon);
+        let u_diff = u.clone().diff();
+        let distinct = u
+            .try_roots(
+                epsilon,
+                max_iter,
+                1,
+                None,
+                Some(AllRootsAlgorithms::StructuredQR),
+                None,
+            )
+            .map_err(|_| Error::max_iter_user())?;
 
-        let mut roots = vec![];
-        let mut this = self.clone();
+        // at a root of multiplicity `m`, `v(z) = m u'(z)`
+        let mut roots: Vec<_> = distinct
+            .into_iter()
+            .map(|z| {
+                let m = (v.eval_point(z) / u_diff.eval_point(z)).re;
+                (
+                    z,
+                    Float::max(Float::round(m), T::one())
+                        .to_usize()
+                        .unwrap_or(1),
+                )
+            })
+            .collect();
+        if roots.iter().map(|(_, m)| m).sum::<usize>() != self.len_raw() - 1 {
+            return Err(Error::pathological());
+        }
 
-        // a small safe number, this is often done in LAPACK to avoid overflows
-        let small_num = Float::sqrt(T::min_positive_value()) / T::epsilon();
-
-        for _ in 0..max_tries {
-            let mut needs_unshifting = false;
-            // leading zero coefficients can be problematic, so shifting to
-            // avoid (the householder reflectors cannot be constructed if the
-            // first element is zero)
-            if this.0[0].abs() < small_num {
-                needs_unshifting = true;
-                this = this.translate(Complex::one(), Complex::zero());
-            }
-            let maybe_roots = match algorithm {
-                AllRootsAlgorithms::Schur | AllRootsAlgorithms::FrancisQR => {
-                    this.roots_francis_qr(epsilon, max_iter)
-                }
-            };
-            if let Ok(mut found_roots) = maybe_roots {
-                if needs_unshifting {
-                    for r in &mut found_roots {
-                        *r -= Complex::one();
-                    }
+        self.refine_multiple_roots(&mut roots, max_iter);
+        Ok(roots)
+    }
+
+    /// Rigorous a posteriori error bounds for approximations of all roots of
+    /// the polynomial, e.g. the output of [`Poly::try_roots`].
+    ///
+    /// The disk around `z_i` has radius `n |p(z_i)| / |a_n prod_{j != i} (z_i - z_j)|`,
+    /// where `n` is the degree. The union of the disks contains all the roots,
+    /// and any connected component made of `m` disks contains exactly `m` roots,
+    /// so an isolated disk contains exactly one root. Rounding errors in
+    /// evaluating the polynomial and the products are accounted for.
+    ///
+    /// Ref: D. A. Bini, G. Fiorentino "Design, analysis, and implementation of
+    /// a multiprecision polynomial rootfinder"
+    /// [DOI](https://doi.org/10.1023/A:1019199917103)
+    ///
+    /// # Panics
+    /// If the number of approximations is not equal to the degree.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use num::Complex;
+    ///
+    /// let p = poly![2.0, -3.0, 1.0];
+    /// let disks = p.root_inclusion_disks(&[Complex::new(1.01, 0.0), Complex::new(1.99, 0.0)]);
+    /// assert!(disks.iter().all(|d| d.isolated));
+    /// assert!((disks[0].center - 1.0).norm() <= disks[0].radius);
+    /// ```
+    #[must_use]
+    pub fn root_inclusion_disks(&self, roots: &[Complex<T>]) -> Vec<InclusionDisk<T>> {
+        debug_assert!(self.is_normalized());
+        assert_eq!(
+            roots.len() + 1,
+            self.len_raw(),
+            "an approximation of every root is needed"
+        );
+
+        let n = T::from_usize(roots.len()).expect("infallible");
+        // bounds on the relative rounding errors of Horner's scheme, and of
+        // the products and quotients in the weight
+        let eval_err = T::from_usize(2 * roots.len() + 1).expect("infallible") * T::epsilon();
+        let weight_err = T::from_usize(4 * roots.len() + 4).expect("infallible") * T::epsilon();
+
+        let radii: Vec<T> = (0..roots.len())
+            .map(|i| {
+                let (value, weight, bound) = weierstrass_factors(self.as_slice(), roots, i);
+                let radius = n * (value.norm() + eval_err * bound) * weight.norm();
+                // coinciding approximations give no information
+                if radius.is_nan() {
+                    return T::infinity();
                 }
-                roots.extend(found_roots);
-                // TODO: sort
-                return Ok(roots);
-            }
-            let err = maybe_roots.expect_err("infallible");
-            if !matches!(err.source, ErrorKind::MaxIterInner) {
-                // only recover if MaxIterInner
-                break;
-            }
+                radius * (T::one() + weight_err)
+            })
+            .collect();
 
-            // uses one iteration of single-root algorithm for recovery when
-            // the multi-root algorithm gets stuck (for pathological cases like
-            // Legendre polynomials), this shrinks the problem by 1 degree
-            // and moves around the coefficients so they are not pathologic anymore
-            let r = match recovery_algorithm {
-                OneRootAlgorithms::Newton => this
-                    .clone()
-                    .one_root_newton(None, epsilon, max_recovery_iter)
-                    .map_err(|_| roots.clone())?,
-                OneRootAlgorithms::Halley => this
-                    .clone()
-                    .one_root_halley(None, epsilon, max_recovery_iter)
-                    .map_err(|_| roots.clone())?,
-                OneRootAlgorithms::JenkinsTraub => unimplemented!(),
-            };
-            roots.push(r.clone());
-            this = this / Poly::from_roots(&[r.clone()]);
-        }
+        (0..roots.len())
+            .map(|i| InclusionDisk {
+                center: roots[i],
+                radius: radii[i],
+                isolated: (0..roots.len())
+                    .filter(|&j| j != i)
+                    .all(|j| (roots[i] - roots[j]).norm() > radii[i] + radii[j]),
+            })
+            .collect()
+    }
 
-        Err(roots)
+    /// Divide by `z - root` in `O(n)`, returning the quotient and an estimate
+    /// of the error, which is the largest coefficient of the discarded
+    /// remainder, zero if `root` is an exact root.
+    ///
+    /// The division runs forward from the leading coefficient for roots that
+    /// are small compared to the others, and backward from the constant term
+    /// otherwise, so the deflation stays stable regardless of the order in
+    /// which the roots are removed.
+    ///
+    /// # Panics
+    /// If the polynomial is constant.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use num::Complex;
+    ///
+    /// let p = poly![-6.0, 11.0, -6.0, 1.0];
+    /// let (q, error) = p.deflate_linear(Complex::new(3.0, 0.0));
+    /// assert_eq!(q, poly![2.0, -3.0, 1.0]);
+    /// assert_eq!(error, 0.0);
+    /// ```
+    #[must_use]
+    pub fn deflate_linear(&self, root: Complex<T>) -> (Self, T) {
+        self.deflate_monic(&[-root, Complex::one()])
+    }
+
+    /// Divide by `(z - root)(z - root*)` in `O(n)`, i.e. remove a pair of
+    /// complex conjugate roots, see [`Poly::deflate_linear`]. The divisor has
+    /// real coefficients, so a polynomial with real coefficients stays real.
+    ///
+    /// # Panics
+    /// If the degree of the polynomial is less than two.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    /// use num::Complex;
+    ///
+    /// // (z^2 + 1)(z - 2)
+    /// let p = poly![-2.0, 1.0, -2.0, 1.0];
+    /// let (q, error) = p.deflate_quadratic(Complex::new(0.0, 1.0));
+    /// assert_eq!(q, poly![-2.0, 1.0]);
+    /// assert_eq!(error, 0.0);
+    /// ```
+    #[must_use]
+    pub fn deflate_quadratic(&self, root: Complex<T>) -> (Self, T) {
+        let sum = root.re + root.re;
+        let product = root.norm_sqr();
+        self.deflate_monic(&[Complex::from(product), Complex::from(-sum), Complex::one()])
     }
 }
 
@@ -310,7 +1387,10 @@ mod test {
     use num::complex::{Complex64, ComplexFloat};
 
     use crate::__util::test::binary_coeffs;
-    use crate::{poly::roots::OneRootAlgorithms, Poly, Poly64};
+    use crate::{
+        poly::roots::{AllRootsAlgorithms, OneRootAlgorithms},
+        Poly, Poly64,
+    };
 
     #[test]
     fn initial_guess_smallest() {
@@ -351,6 +1431,108 @@ mod test {
         assert!((Poly::from_roots(&roots) - p).almost_zero(&1E-14));
     }
 
+    #[test]
+    fn roots_laguerre() {
+        let p = poly![1.0, 0.0, 1.0, 0.0, 1.0];
+        let roots = p
+            .try_n_roots(4, None, 1E-14, 20, Some(OneRootAlgorithms::Laguerre))
+            .unwrap();
+        assert!((Poly::from_roots(&roots) - p).almost_zero(&1E-14));
+    }
+
+    #[test]
+    fn roots_laguerre_legendre() {
+        let p = Poly64::legendre(16);
+        let roots = p
+            .try_n_roots(16, None, 1E-12, 100, Some(OneRootAlgorithms::Laguerre))
+            .unwrap();
+        for r in roots {
+            assert!(r.im.abs() < 1E-10);
+            assert!(p.eval_point(r).norm() < 1E-10);
+        }
+    }
+
+    #[test]
+    fn laguerre_recovery() {
+        let p = Poly64::legendre(10);
+        let roots = p
+            .try_roots(
+                1E-12,
+                3,
+                20,
+                Some(100),
+                Some(AllRootsAlgorithms::Aberth),
+                Some(OneRootAlgorithms::Laguerre),
+            )
+            .unwrap();
+        assert_eq!(roots.len(), 10);
+        assert!(roots.iter().all(|&r| p.eval_point(r).norm() < 1E-10));
+    }
+
+    #[test]
+    fn try_n_roots_deflates_by_synthetic_division() {
+        // the largest root comes first, so it is deflated backward
+        let expected = [1E-3, 0.5, 2.0, 1E3].map(|r| Complex64::new(r, 0.0));
+        let p = Poly::from_roots(&expected);
+        let guess = Complex64::new(1.1E3, 1.0);
+        let roots = p
+            .try_n_roots(4, Some(guess), 1E-12, 100, Some(OneRootAlgorithms::Newton))
+            .unwrap();
+        let mut this = p.clone();
+        for &r in &roots {
+            let (z, _) = this
+                .one_root(OneRootAlgorithms::Newton, Some(guess), 1E-12, 100)
+                .unwrap();
+            assert_eq!(z, r);
+            this = this.deflate_linear(r).0;
+        }
+        assert!((roots[0] - expected[3]).norm() < 1E-9);
+        for e in expected {
+            assert!(roots.iter().any(|r| (r - e).norm() < 1E-9 * e.norm()));
+        }
+    }
+
+    #[test]
+    fn deflate_linear_both_directions() {
+        let roots = [1E-3, 0.5, 2.0, 1E3].map(|r| Complex64::new(r, 0.0));
+        let p = Poly::from_roots(&roots);
+        // the smallest root is deflated forward, the largest backward
+        for (i, r) in roots.iter().enumerate() {
+            let mut rest = roots.to_vec();
+            rest.remove(i);
+            let expected = Poly::from_roots(&rest);
+            let (deflated, error) = p.deflate_linear(*r);
+            assert!(error <= 1E-12);
+            assert_eq!(deflated.len_raw(), expected.len_raw());
+            for (a, b) in deflated.iter().zip(expected.iter()) {
+                assert!((a - b).norm() <= 1E-12 * b.norm().max(1.0));
+            }
+        }
+        let (_, error) = p.deflate_linear(Complex64::new(0.4, 0.0));
+        assert!(error > 1E-3);
+    }
+
+    #[test]
+    fn deflate_quadratic_conjugate_pairs() {
+        let roots = [
+            Complex64::new(0.1, 0.2),
+            Complex64::new(0.1, -0.2),
+            Complex64::new(3.0, 0.0),
+            Complex64::new(-20.0, 10.0),
+            Complex64::new(-20.0, -10.0),
+        ];
+        let p = Poly::from_roots(&roots);
+        for (pair, rest) in [(roots[0], &roots[2..]), (roots[3], &roots[..3])] {
+            let (deflated, error) = p.deflate_quadratic(pair);
+            let expected = Poly::from_roots(rest);
+            assert!(error <= 1E-10);
+            for (a, b) in deflated.iter().zip(expected.iter()) {
+                assert!((a - b).norm() <= 1E-12 * b.norm().max(1.0));
+                assert_eq!(a.im, 0.0);
+            }
+        }
+    }
+
     /// This test is to find the minimum number of iterations at various degrees
     /// that achieves 90% success rate, using "binary coefficients" polynomials,
     /// which are difficult for the nalgebra implementation of the Schur
@@ -412,4 +1594,434 @@ mod test {
         assert_eq!(roots[1].re(), -1.5);
         assert!((roots[1].im().abs() - 0.866) < 0.01);
     }
+
+    #[test]
+    fn jenkins_traub_roots_of_reverse_bessel() {
+        let poly = Poly64::reverse_bessel(2).unwrap();
+        let roots = poly
+            .try_n_roots(2, None, 1E-14, 1000, Some(OneRootAlgorithms::JenkinsTraub))
+            .unwrap();
+        assert!((roots[0].re() + 1.5).abs() < 1E-12);
+        assert!((roots[0].im().abs() - 0.866).abs() < 0.01);
+        assert!((roots[1].re() + 1.5).abs() < 1E-12);
+        assert!((roots[1].im().abs() - 0.866).abs() < 0.01);
+    }
+
+    #[test]
+    fn jenkins_traub_big_reverse_bessel() {
+        let poly = Poly64::reverse_bessel(10).unwrap();
+        let roots = poly
+            .try_n_roots(10, None, 1E-8, 1000, Some(OneRootAlgorithms::JenkinsTraub))
+            .unwrap();
+        assert_eq!(roots.len(), 10);
+        let scale = poly[0].norm();
+        for r in roots {
+            // reverse bessel polynomials are Hurwitz stable
+            assert!(r.re() < 0.0);
+            assert!(poly.eval_point(r).norm() / scale < 1E-10);
+        }
+    }
+
+    #[test]
+    fn jenkins_traub_roots_of_legendre() {
+        let poly = Poly64::legendre(7);
+        let roots = poly
+            .try_n_roots(7, None, 1E-12, 1000, Some(OneRootAlgorithms::JenkinsTraub))
+            .unwrap();
+        for r in &roots {
+            // roots of legendre polynomials are real and in (-1, 1)
+            assert!(r.im().abs() < 1E-6);
+            assert!(r.re().abs() < 1.0);
+        }
+        let mut roots_re = roots.iter().map(|r| r.re()).collect_vec();
+        roots_re.sort_by(f64::total_cmp);
+        assert!((roots_re[3]).abs() < 1E-6);
+        assert!((roots_re[6] - 0.949_107_912_342_758_5).abs() < 1E-6);
+    }
+
+    #[test]
+    fn multishift_qr_roots_of_unity() {
+        // z^200 - 1
+        let mut coeffs = vec![0.0; 201];
+        coeffs[0] = -1.0;
+        coeffs[200] = 1.0;
+        let poly = Poly64::from_real_slice(&coeffs);
+        let roots = poly
+            .try_roots(
+                1E-14,
+                1000,
+                1,
+                None,
+                Some(AllRootsAlgorithms::MultishiftQR),
+                None,
+            )
+            .unwrap();
+        assert_eq!(roots.len(), 200);
+        for r in &roots {
+            assert!((r.norm() - 1.0).abs() < 1E-10);
+        }
+    }
+
+    #[test]
+    fn multishift_qr_big_from_roots() {
+        let expected = (0..120)
+            .map(|k| {
+                let t = f64::from(k);
+                Complex64::from_polar(0.5 + (t * 0.37).sin().abs(), t * 2.1)
+            })
+            .collect_vec();
+        let poly = Poly::from_roots(&expected);
+        let roots = poly
+            .try_roots(
+                1E-14,
+                1000,
+                1,
+                None,
+                Some(AllRootsAlgorithms::MultishiftQR),
+                None,
+            )
+            .unwrap();
+        assert_eq!(roots.len(), 120);
+        let max_coeff = poly.iter().map(|c| c.norm()).fold(0.0, f64::max);
+        for r in &roots {
+            // normwise backward error
+            let scale = max_coeff * (0..=120).map(|k| r.norm().powi(k)).sum::<f64>();
+            assert!(poly.eval_point(*r).norm() / scale < 1E-12);
+        }
+    }
+
+    #[test]
+    fn multishift_qr_roots_of_reverse_bessel() {
+        let poly = Poly64::reverse_bessel(50).unwrap();
+        let roots = poly
+            .try_roots(
+                1E-14,
+                1000,
+                1,
+                None,
+                Some(AllRootsAlgorithms::MultishiftQR),
+                None,
+            )
+            .unwrap();
+        assert_eq!(roots.len(), 50);
+        for r in &roots {
+            assert!(r.re().is_finite() && r.im().is_finite());
+            // reverse bessel polynomials are Hurwitz stable
+            assert!(r.re() < 0.0);
+        }
+    }
+
+    #[test]
+    fn newton_polygon_guesses() {
+        let expected = [1E-3, 1.0, 1E3].map(|r| Complex64::new(r, 0.0));
+        let poly = Poly::from_roots(&expected);
+        let guesses = poly.initial_root_guesses();
+        assert_eq!(guesses.len(), 3);
+        for (guess, root) in guesses.iter().zip(expected) {
+            assert!((guess.norm() / root.norm()).log10().abs() < 0.5);
+        }
+    }
+
+    #[test]
+    fn newton_from_polygon_guesses() {
+        let expected = [1E-3, 1.0, 1E3].map(|r| Complex64::new(r, 0.0));
+        let poly = Poly::from_roots(&expected);
+        let roots = poly
+            .try_n_roots(3, None, 1E-14, 100, Some(OneRootAlgorithms::Newton))
+            .unwrap();
+        for (root, expected) in roots.iter().zip(expected) {
+            assert!((root - expected).norm() < 1E-12 * expected.norm());
+        }
+        assert!(poly![2.0].initial_root_guesses().is_empty());
+    }
+
+    #[test]
+    fn newton_stops_at_rounding_noise() {
+        // the residual at the closest floating point number to the root is
+        // not zero, but Newton's method stops there even with a tolerance of
+        // zero
+        let poly: Poly64 = poly![-2.0, 0.0, 1.0];
+        let roots = poly
+            .try_n_roots(
+                1,
+                Some(complex!(1.0)),
+                0.0,
+                100,
+                Some(OneRootAlgorithms::Newton),
+            )
+            .unwrap();
+        assert!((roots[0] - 2.0.sqrt()).norm() <= f64::EPSILON);
+    }
+
+    #[test]
+    fn aberth_roots_of_unity() {
+        // z^500 - 1
+        let mut coeffs = vec![0.0; 501];
+        coeffs[0] = -1.0;
+        coeffs[500] = 1.0;
+        let poly = Poly64::from_real_slice(&coeffs);
+        let roots = poly
+            .try_roots(1E-14, 100, 1, None, Some(AllRootsAlgorithms::Aberth), None)
+            .unwrap();
+        assert_eq!(roots.len(), 500);
+        for r in &roots {
+            assert!((r.norm() - 1.0).abs() < 1E-12);
+        }
+    }
+
+    #[test]
+    fn aberth_roots_of_legendre() {
+        let poly = Poly64::legendre(20);
+        let roots = poly
+            .try_roots(1E-14, 100, 1, None, Some(AllRootsAlgorithms::Aberth), None)
+            .unwrap();
+        assert_eq!(roots.len(), 20);
+        for r in &roots {
+            // roots of legendre polynomials are real and in (-1, 1)
+            assert!(r.im().abs() < 1E-8);
+            assert!(r.re().abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn aberth_multiple_roots() {
+        let expected = [1.0, 1.0, 1.0, 2.0, 2.0, -3.0].map(|r| Complex64::new(r, 0.0));
+        let poly = Poly::from_roots(&expected);
+        let roots = poly
+            .try_roots(1E-14, 1000, 1, None, Some(AllRootsAlgorithms::Aberth), None)
+            .unwrap();
+        // multiple roots are ill-conditioned, a root of multiplicity m can
+        // only be found with about 1/m of the available precision
+        for e in expected {
+            assert!(roots.iter().any(|r| (r - e).norm() < 1E-4));
+        }
+    }
+
+    #[test]
+    fn aberth_epsilon_is_absolute() {
+        // a loose tolerance on a polynomial with large coefficients, where the
+        // rounding error doesn't stop the iteration before `epsilon` does
+        let expected = [1.0, 2.0, 3.0, -4.0].map(|r| Complex64::new(r, 0.0));
+        let poly = Poly::from_roots(&expected) * Complex64::new(1E8, 0.0);
+        let guesses = poly.initial_root_guesses();
+        let roots = poly.roots_aberth(guesses, 1E-4, 100).unwrap();
+        for (r, _) in roots {
+            assert!(poly.eval_point(r).norm() <= 1E-4);
+        }
+    }
+
+    #[test]
+    fn durand_kerner_epsilon_is_absolute() {
+        let expected = [1.0, 2.0, 3.0, -4.0].map(|r| Complex64::new(r, 0.0));
+        let poly = Poly::from_roots(&expected) * Complex64::new(1E8, 0.0);
+        let guesses = poly.initial_root_guesses();
+        let roots = poly.roots_durand_kerner(guesses, 1E-4, 1000).unwrap();
+        for (r, _) in roots {
+            assert!(poly.eval_point(r).norm() <= 1E-4);
+        }
+    }
+
+    #[test]
+    fn durand_kerner_roots_of_unity() {
+        // z^100 - 1
+        let mut coeffs = vec![0.0; 101];
+        coeffs[0] = -1.0;
+        coeffs[100] = 1.0;
+        let poly = Poly64::from_real_slice(&coeffs);
+        let roots = poly
+            .try_roots(
+                1E-14,
+                1000,
+                1,
+                None,
+                Some(AllRootsAlgorithms::DurandKerner),
+                None,
+            )
+            .unwrap();
+        assert_eq!(roots.len(), 100);
+        for r in &roots {
+            assert!((r.norm() - 1.0).abs() < 1E-12);
+        }
+    }
+
+    /// Durand-Kerner as an independent cross-check of the QR algorithm
+    #[test]
+    fn durand_kerner_agrees_with_multishift_qr() {
+        let expected = (1..=12)
+            .map(|k| Complex64::from_polar(f64::from(k) / 4.0, f64::from(k)))
+            .collect_vec();
+        let poly = Poly::from_roots(&expected);
+        let roots_dk = poly
+            .try_roots(
+                1E-14,
+                1000,
+                1,
+                None,
+                Some(AllRootsAlgorithms::DurandKerner),
+                None,
+            )
+            .unwrap();
+        let roots_qr = poly
+            .try_roots(
+                1E-14,
+                1000,
+                1,
+                None,
+                Some(AllRootsAlgorithms::MultishiftQR),
+                None,
+            )
+            .unwrap();
+        assert_eq!(roots_dk.len(), 12);
+        for r in &roots_qr {
+            assert!(roots_dk.iter().any(|s| (s - r).norm() < 1E-8));
+        }
+    }
+
+    #[test]
+    fn structured_qr_roots_of_unity() {
+        // z^500 - 1
+        let mut coeffs = vec![0.0; 501];
+        coeffs[0] = -1.0;
+        coeffs[500] = 1.0;
+        let poly = Poly64::from_real_slice(&coeffs);
+        let roots = poly
+            .try_roots(
+                1E-14,
+                1000,
+                1,
+                None,
+                Some(AllRootsAlgorithms::StructuredQR),
+                None,
+            )
+            .unwrap();
+        assert_eq!(roots.len(), 500);
+        for r in &roots {
+            assert!((r.norm() - 1.0).abs() < 1E-12);
+        }
+    }
+
+    #[test]
+    fn structured_qr_big_from_roots() {
+        let expected = (0..120)
+            .map(|k| {
+                let t = f64::from(k);
+                Complex64::from_polar(0.5 + (t * 0.37).sin().abs(), t * 2.1)
+            })
+            .collect_vec();
+        let poly = Poly::from_roots(&expected);
+        let roots = poly
+            .try_roots(
+                1E-14,
+                1000,
+                1,
+                None,
+                Some(AllRootsAlgorithms::StructuredQR),
+                None,
+            )
+            .unwrap();
+        assert_eq!(roots.len(), 120);
+        let max_coeff = poly.iter().map(|c| c.norm()).fold(0.0, f64::max);
+        for r in &roots {
+            // normwise backward error
+            let scale = max_coeff * (0..=120).map(|k| r.norm().powi(k)).sum::<f64>();
+            assert!(poly.eval_point(*r).norm() / scale < 1E-13);
+        }
+    }
+
+    #[test]
+    fn structured_qr_zero_roots() {
+        let expected = [0.0, 0.0, 1.0, -2.0].map(|r| Complex64::new(r, 0.0));
+        let poly = Poly::from_roots(&expected);
+        let roots = poly
+            .try_roots(
+                1E-14,
+                1000,
+                1,
+                None,
+                Some(AllRootsAlgorithms::StructuredQR),
+                None,
+            )
+            .unwrap();
+        assert_eq!(roots.len(), 4);
+        for e in expected {
+            assert!(roots.iter().any(|r| (r - e).norm() < 1E-12));
+        }
+    }
+
+    #[test]
+    fn roots_with_multiplicity_quintuple() {
+        let poly = Poly::from_roots(&[Complex64::new(1.0, 0.0); 5]);
+        let roots = poly.roots_with_multiplicity(1E-10, 100).unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].1, 5);
+        assert!((roots[0].0 - 1.0).norm() < 1E-14);
+    }
+
+    #[test]
+    fn roots_with_multiplicity_mixed() {
+        let expected = [
+            (Complex64::new(1.0, 0.0), 3),
+            (Complex64::new(-2.0, 0.0), 2),
+            (Complex64::new(0.0, 1.0), 1),
+            (Complex64::new(0.5, 0.5), 4),
+        ];
+        let all = expected
+            .iter()
+            .flat_map(|&(z, m)| std::iter::repeat(z).take(m))
+            .collect_vec();
+        let poly = Poly::from_roots(&all);
+        let roots = poly.roots_with_multiplicity(1E-10, 100).unwrap();
+        assert_eq!(roots.len(), expected.len());
+        for (z, m) in expected {
+            assert!(roots.iter().any(|&(r, n)| n == m && (r - z).norm() < 1E-13));
+        }
+    }
+
+    #[test]
+    fn roots_with_multiplicity_simple() {
+        let poly = Poly64::legendre(10);
+        let roots = poly.roots_with_multiplicity(1E-10, 100).unwrap();
+        assert_eq!(roots.len(), 10);
+        assert!(roots.iter().all(|&(_, m)| m == 1));
+    }
+
+    #[test]
+    fn inclusion_disks_contain_roots() {
+        let expected = [1.0, 2.0, 3.0].map(|r| Complex64::new(r, 0.0));
+        let poly = Poly::from_roots(&expected);
+        let approx = [1.001, 1.998, 3.002].map(|r| Complex64::new(r, 0.0));
+        let disks = poly.root_inclusion_disks(&approx);
+        for (disk, root) in disks.iter().zip(expected) {
+            assert!(disk.isolated);
+            assert!((disk.center - root).norm() <= disk.radius);
+            assert!(disk.radius < 0.1);
+        }
+    }
+
+    #[test]
+    fn inclusion_disks_of_cluster() {
+        let poly = Poly::from_roots(&[Complex64::new(1.0, 0.0); 2]);
+        let approx = [
+            Complex64::new(1.0 + 1E-9, 0.0),
+            Complex64::new(1.0 - 1E-9, 0.0),
+        ];
+        let disks = poly.root_inclusion_disks(&approx);
+        for disk in &disks {
+            assert!(!disk.isolated);
+            assert!((disk.center - 1.0).norm() <= disk.radius);
+        }
+    }
+
+    #[test]
+    fn inclusion_disks_certify_roots_of_unity() {
+        let mut coeffs = vec![0.0; 65];
+        coeffs[0] = -1.0;
+        coeffs[64] = 1.0;
+        let poly = Poly64::from_real_slice(&coeffs);
+        let roots = poly
+            .try_roots(1E-14, 1000, 1, None, Some(AllRootsAlgorithms::Aberth), None)
+            .unwrap();
+        let disks = poly.root_inclusion_disks(&roots);
+        assert!(disks.iter().all(|d| d.isolated && d.radius < 1E-12));
+    }
 }
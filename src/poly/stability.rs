@@ -0,0 +1,495 @@
+//! Stability tests, which count the roots in the stability region without
+//! computing them.
+
+use na::RealField;
+use num::{Complex, Float, Zero};
+
+use crate::{Poly, Scalar};
+
+/// How many times the singular cases are retried with a larger perturbation
+const MAX_PERTURBATIONS: usize = 4;
+
+/// The number of roots in each part of the complex plane relative to a
+/// stability region, counted with multiplicity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct StabilityCounts {
+    /// Roots strictly inside the region
+    pub stable: usize,
+
+    /// Roots on the boundary of the region, up to a small relative
+    /// tolerance
+    pub marginal: usize,
+
+    /// Roots strictly outside the region
+    pub unstable: usize,
+}
+
+impl StabilityCounts {
+    /// All roots are strictly inside the stability region
+    #[must_use]
+    pub const fn is_stable(&self) -> bool {
+        self.marginal == 0 && self.unstable == 0
+    }
+}
+
+/// The result of [`Poly::schur_cohn`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchurCohn<T: Scalar> {
+    /// The Schur transforms `T^k p`, `k = 1, ..., n`, each normalized so that
+    /// its largest coefficient has modulus one. The table stops early if a
+    /// transform vanishes at zero, which is the singular case.
+    pub table: Vec<Poly<T>>,
+
+    /// Roots inside, on and outside of the unit circle
+    pub counts: StabilityCounts,
+}
+
+/// The result of [`Poly::routh_array`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouthArray<T> {
+    /// The rows of the array, from the row of `s^n` to the row of `s^0`,
+    /// padded with zeros. Zero pivots are replaced by a small positive
+    /// number, and rows of zeros by the derivative of the auxiliary
+    /// polynomial.
+    pub table: Vec<Vec<T>>,
+
+    /// Roots in the left half-plane, on the imaginary axis and in the right
+    /// half-plane
+    pub counts: StabilityCounts,
+}
+
+impl<T: Scalar + RealField + Float> Poly<T> {
+    /// Counts the roots inside, on and outside of the unit circle with the
+    /// Schur-Cohn test, which is equivalent to Jury's table.
+    ///
+    /// The Schur transform of `q` of degree `m` is
+    /// `T q(z) = conj(q(0)) q(z) - q_m z^m conj(q(1 / conj(z)))`, which has
+    /// a lower degree. By Rouché's theorem, it has as many roots inside the
+    /// unit circle as `q` if `T q(0) > 0`, and as many as `q` has outside if
+    /// `T q(0) < 0`. So if `T^k p(0)` is non-zero for all `k`, there are no
+    /// roots on the unit circle, and the signs determine how many are inside.
+    ///
+    /// The singular cases, where some `δ_k` vanishes up to rounding errors,
+    /// occur if roots are on the circle or placed symmetrically with respect
+    /// to it. Then the roots are counted inside slightly smaller and larger
+    /// circles, so the roots within a relative distance of about
+    /// `sqrt(epsilon)` of the unit circle are counted as on the circle.
+    ///
+    /// A constant or the zero polynomial has no roots, the table is empty and
+    /// all counts are zero.
+    ///
+    /// Ref: P. Henrici "Applied and Computational Complex Analysis, Vol. 1",
+    /// Section 6.8
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::Poly;
+    /// use num::Complex;
+    ///
+    /// let p = Poly::from_roots(&[
+    ///     Complex::new(0.5, 0.5),
+    ///     Complex::new(0.5, -0.5),
+    ///     Complex::new(-1.0, 0.0),
+    ///     Complex::new(0.0, 3.0),
+    /// ]);
+    /// let counts = p.schur_cohn().counts;
+    /// assert_eq!((counts.stable, counts.marginal, counts.unstable), (2, 1, 1));
+    /// ```
+    #[must_use]
+    pub fn schur_cohn(&self) -> SchurCohn<T> {
+        debug_assert!(self.is_normalized());
+
+        if self.len_raw() < 2 {
+            return SchurCohn {
+                table: vec![],
+                counts: StabilityCounts::default(),
+            };
+        }
+        let degree = self.len_raw() - 1;
+        let (table, inside) = self.schur_cohn_table();
+        if let Some(stable) = inside {
+            return SchurCohn {
+                table,
+                counts: StabilityCounts {
+                    stable,
+                    marginal: 0,
+                    unstable: degree - stable,
+                },
+            };
+        }
+
+        // count in the unit circle scaled by `1 - h` and `1 + h`, by
+        // scaling the argument of the polynomial
+        let mut h = Float::sqrt(T::epsilon());
+        for _ in 0..MAX_PERTURBATIONS {
+            let (_, inner) = self.clone().scale_argument(T::one() - h).schur_cohn_table();
+            let (_, outer) = self.clone().scale_argument(T::one() + h).schur_cohn_table();
+            if let (Some(inner), Some(outer)) = (inner, outer) {
+                let outer = outer.max(inner);
+                return SchurCohn {
+                    table,
+                    counts: StabilityCounts {
+                        stable: inner,
+                        marginal: outer - inner,
+                        unstable: degree - outer,
+                    },
+                };
+            }
+            h += h;
+        }
+        // roots everywhere close to the circle, none can be told apart from
+        // it
+        SchurCohn {
+            table,
+            counts: StabilityCounts {
+                stable: 0,
+                marginal: degree,
+                unstable: 0,
+            },
+        }
+    }
+
+    /// All roots are strictly inside the unit circle, i.e. the polynomial is
+    /// the denominator of a stable discrete-time system, see
+    /// [`Poly::schur_cohn`].
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    ///
+    /// // z^2 - z + 0.5
+    /// assert!(poly![0.5, -1.0, 1.0].is_schur_stable());
+    /// // z^2 - 2.5z + 1
+    /// assert!(!poly![1.0, -2.5, 1.0].is_schur_stable());
+    /// ```
+    #[must_use]
+    pub fn is_schur_stable(&self) -> bool {
+        self.schur_cohn().counts.is_stable()
+    }
+
+    /// Counts the roots in the left half-plane, on the imaginary axis and in
+    /// the right half-plane with the Routh array. The imaginary parts of the
+    /// coefficients are ignored.
+    ///
+    /// The number of sign changes in the first column is the number of roots
+    /// in the right half-plane. A zero pivot is replaced by a small positive
+    /// number, the `ε` method. A row of zeros means that the row above is
+    /// the auxiliary polynomial, a factor whose roots are symmetric with
+    /// respect to the origin, and it is replaced by its derivative. The
+    /// roots of the first auxiliary polynomial that are not in the right
+    /// half-plane, nor mirrored from it, are on the imaginary axis.
+    ///
+    /// Entries are considered zero if they are within the rounding errors of
+    /// their computation.
+    ///
+    /// A constant or the zero polynomial has no roots, the table is empty and
+    /// all counts are zero.
+    ///
+    /// Ref: E. J. Routh "A Treatise on the Stability of a Given State of
+    /// Motion", 1877
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly};
+    ///
+    /// // (s + 2)(s^2 + 1)^2
+    /// let p: Poly<f64> = poly![2.0, 1.0, 4.0, 2.0, 2.0, 1.0];
+    /// let routh = p.routh_array();
+    /// assert_eq!(routh.table[0], vec![1.0, 2.0, 1.0]);
+    /// let counts = routh.counts;
+    /// assert_eq!((counts.stable, counts.marginal, counts.unstable), (1, 4, 0));
+    /// ```
+    #[must_use]
+    pub fn routh_array(&self) -> RouthArray<T> {
+        debug_assert!(self.is_normalized());
+
+        if self.len_raw() < 2 {
+            return RouthArray {
+                table: vec![],
+                counts: StabilityCounts::default(),
+            };
+        }
+        let (table, auxiliary) = self.routh_table();
+        let degree = table.len() - 1;
+        let sign_changes = |from: usize| {
+            table[from..]
+                .windows(2)
+                .filter(|w| (w[0][0] < T::zero()) != (w[1][0] < T::zero()))
+                .count()
+        };
+        let unstable = sign_changes(0);
+        let marginal = auxiliary.map_or(0, |(k, m)| m.saturating_sub(2 * sign_changes(k)));
+        let counts = StabilityCounts {
+            stable: degree.saturating_sub(unstable + marginal),
+            marginal,
+            unstable,
+        };
+        RouthArray { table, counts }
+    }
+
+    /// All roots are strictly in the left half-plane, i.e. the polynomial is
+    /// the denominator of a stable continuous-time system, see
+    /// [`Poly::routh_array`].
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_poly::{poly, Poly, Poly64};
+    ///
+    /// assert!(Poly64::reverse_bessel(10).unwrap().is_hurwitz_stable());
+    /// // s^4 + s^3 + 2s^2 + 2s + 3 has two roots in the right half-plane
+    /// assert!(!poly![3.0, 2.0, 2.0, 1.0, 1.0].is_hurwitz_stable());
+    /// ```
+    #[must_use]
+    pub fn is_hurwitz_stable(&self) -> bool {
+        self.routh_array().counts.is_stable()
+    }
+}
+
+// private
+impl<T: Scalar + RealField + Float> Poly<T> {
+    /// The rows of the Routh array, and the index of the row of the first
+    /// auxiliary polynomial with its degree, if any
+    fn routh_table(&self) -> (Vec<Vec<T>>, Option<(usize, usize)>) {
+        debug_assert!(self.len_raw() >= 2);
+
+        let coeffs: Vec<T> = self.iter().rev().map(|c| c.re).collect();
+        let degree = coeffs.len() - 1;
+        let width = degree / 2 + 1;
+        let row = |parity: usize| -> Vec<T> {
+            let mut row: Vec<T> = coeffs.iter().skip(parity).step_by(2).copied().collect();
+            row.resize(width, T::zero());
+            row
+        };
+        let tol = T::from_usize(8 * (degree + 1)).expect("infallible") * T::epsilon();
+        let small = Float::sqrt(T::epsilon());
+
+        let mut table = vec![row(0)];
+        // the row of the first auxiliary polynomial and its degree
+        let mut auxiliary = None;
+        for k in 1..=degree {
+            let mut next = if k == 1 {
+                row(1)
+            } else {
+                let (a, b) = (&table[k - 2], &table[k - 1]);
+                let mut next: Vec<T> = (0..width - 1)
+                    .map(|j| {
+                        let c = a[j + 1] - a[0] * b[j + 1] / b[0];
+                        let error =
+                            tol * (Float::abs(a[j + 1]) + Float::abs(a[0] * b[j + 1] / b[0]));
+                        if Float::abs(c) <= error {
+                            T::zero()
+                        } else {
+                            c
+                        }
+                    })
+                    .collect();
+                next.push(T::zero());
+                next
+            };
+            if next.iter().all(Zero::is_zero) {
+                // the derivative of the auxiliary polynomial of degree `m`,
+                // `sum b_j s^(m - 2j)`
+                let m = degree - k + 1;
+                next = table[k - 1]
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &b)| b * T::from_usize(m.saturating_sub(2 * j)).expect("infallible"))
+                    .collect();
+                auxiliary.get_or_insert((k - 1, m));
+            } else if next[0].is_zero() {
+                next[0] = small
+                    * next
+                        .iter()
+                        .fold(T::zero(), |acc, &c| Float::max(acc, Float::abs(c)));
+            }
+            table.push(next);
+        }
+        (table, auxiliary)
+    }
+
+    /// The table of Schur transforms and the number of roots inside the unit
+    /// circle, `None` in the singular case
+    fn schur_cohn_table(&self) -> (Vec<Self>, Option<usize>) {
+        let degree = self.len_raw() - 1;
+        let tol = T::from_usize(8 * (degree + 1)).expect("infallible") * T::epsilon();
+        let mut q: Vec<Complex<T>> = self.iter().copied().collect();
+        let mut table = Vec::with_capacity(degree);
+        let mut negative = Vec::with_capacity(degree);
+        for _ in 0..degree {
+            let m = q.len() - 1;
+            // the coefficient of `z^m` cancels
+            let mut next: Vec<Complex<T>> = (0..m)
+                .map(|j| q[0].conj() * q[j] - q[m] * q[m - j].conj())
+                .collect();
+            let delta = next[0].re;
+            if Float::abs(delta) <= tol * (q[0].norm_sqr() + q[m].norm_sqr()) {
+                return (table, None);
+            }
+            negative.push(delta < T::zero());
+            // only the signs matter, and `T(c q) = |c|^2 T q`
+            let max = next.iter().map(|&c| c.norm()).fold(T::zero(), Float::max);
+            for c in &mut next {
+                *c = c.unscale(max);
+            }
+            table.push(Self::from_complex_slice(&next));
+            q = next;
+        }
+        // the last transform is a non-zero constant without roots, going
+        // back up to `p` of degree `n`
+        let inside =
+            negative.iter().rev().zip(1..).fold(
+                0,
+                |inside, (&negative, m)| {
+                    if negative {
+                        m - inside
+                    } else {
+                        inside
+                    }
+                },
+            );
+        (table, Some(inside))
+    }
+
+    /// `p(s z)`, whose roots are the roots of `p` divided by `s`
+    fn scale_argument(mut self, s: T) -> Self {
+        let mut power = T::one();
+        for c in self.0.iter_mut() {
+            *c = c.scale(power);
+            power *= s;
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num::{complex::Complex64, Zero};
+
+    use crate::{Poly, Poly64, StabilityCounts};
+
+    #[test]
+    fn schur_cohn_agrees_with_moduli() {
+        let cases = [
+            vec![(0.3, 0.1), (0.9, 0.0), (-0.2, -0.95)],
+            vec![(1.5, 0.0), (0.1, 2.0), (0.1, -2.0), (-0.5, 0.0)],
+            vec![(0.0, 0.0), (0.0, 0.0), (3.0, 0.0)],
+            vec![(0.5, 0.0); 4],
+        ];
+        for roots in cases {
+            let roots: Vec<_> = roots
+                .iter()
+                .map(|&(re, im)| Complex64::new(re, im))
+                .collect();
+            let counts = Poly::from_roots(&roots).schur_cohn().counts;
+            let inside = roots.iter().filter(|z| z.norm() < 1.0).count();
+            assert_eq!(counts.stable, inside, "{roots:?}");
+            assert_eq!(counts.marginal, 0);
+            assert_eq!(counts.unstable, roots.len() - inside);
+        }
+    }
+
+    #[test]
+    fn schur_cohn_singular_cases() {
+        // roots on the unit circle
+        let p = Poly::from_roots(&[
+            Complex64::new(0.6, 0.8),
+            Complex64::new(0.6, -0.8),
+            Complex64::new(0.2, 0.0),
+        ]);
+        let counts = p.schur_cohn().counts;
+        assert_eq!((counts.stable, counts.marginal, counts.unstable), (1, 2, 0));
+        assert!(!p.is_schur_stable());
+
+        // roots symmetric with respect to the unit circle
+        let q = Poly::from_roots(&[Complex64::new(0.5, 0.0), Complex64::new(2.0, 0.0)]);
+        let counts = q.schur_cohn().counts;
+        assert_eq!((counts.stable, counts.marginal, counts.unstable), (1, 0, 1));
+    }
+
+    #[test]
+    fn schur_stable_high_order() {
+        // 40 roots close to the unit circle, on either side of it
+        for (radius, stable) in [(0.95, true), (1.05, false)] {
+            let roots: Vec<_> = (0..40)
+                .map(|k| Complex64::from_polar(radius, 0.3 + f64::from(k) * 0.157))
+                .collect();
+            let p = Poly::from_roots(&roots);
+            assert_eq!(p.is_schur_stable(), stable);
+            assert_eq!(p.schur_cohn().counts.unstable, if stable { 0 } else { 40 });
+        }
+        let counts = Poly64::legendre(9).schur_cohn().counts;
+        assert_eq!(counts.stable, 9);
+    }
+
+    #[test]
+    fn schur_cohn_constants() {
+        let constants: [Poly64; 3] = [poly![], poly![1.0], poly![-3.0]];
+        for p in constants {
+            let schur = p.schur_cohn();
+            assert!(schur.table.is_empty());
+            assert_eq!(schur.counts, StabilityCounts::default());
+            assert!(p.is_schur_stable());
+        }
+    }
+
+    #[test]
+    fn routh_agrees_with_roots() {
+        let cases = [
+            vec![-1.0, -2.0, -3.0],
+            vec![-1.0, 2.0, 0.5, -0.1],
+            vec![0.0, -1.0, 2.0],
+            vec![-4.0, -0.5, 3.0, 1.0, -1.0],
+        ];
+        for roots in cases {
+            let p = Poly::from_roots(
+                &roots
+                    .iter()
+                    .map(|&r| Complex64::new(r, 0.0))
+                    .collect::<Vec<_>>(),
+            );
+            let counts = p.routh_array().counts;
+            assert_eq!(
+                counts.unstable,
+                roots.iter().filter(|&&r| r > 0.0).count(),
+                "{roots:?}"
+            );
+            assert_eq!(counts.marginal, roots.iter().filter(|&&r| r == 0.0).count());
+            assert_eq!(counts.stable, roots.iter().filter(|&&r| r < 0.0).count());
+        }
+    }
+
+    #[test]
+    fn routh_special_cases() {
+        // zero pivot: s^4 + s^3 + 2s^2 + 2s + 3
+        let counts = poly![3.0, 2.0, 2.0, 1.0, 1.0].routh_array().counts;
+        assert_eq!((counts.stable, counts.marginal, counts.unstable), (2, 0, 2));
+
+        // zero row from a pair on the imaginary axis and a pair mirrored
+        // across it: (s^2 + 4)(s^2 - 1)(s + 3)
+        let p = Poly::from_roots(&[
+            Complex64::new(0.0, 2.0),
+            Complex64::new(0.0, -2.0),
+            Complex64::new(1.0, 0.0),
+            Complex64::new(-1.0, 0.0),
+            Complex64::new(-3.0, 0.0),
+        ]);
+        let counts = p.routh_array().counts;
+        assert_eq!((counts.stable, counts.marginal, counts.unstable), (2, 2, 1));
+        assert!(!p.is_hurwitz_stable());
+
+        // even polynomial, the zero row comes right away: s^2 + 1
+        let counts = poly![1.0, 0.0, 1.0].routh_array().counts;
+        assert_eq!((counts.stable, counts.marginal, counts.unstable), (0, 2, 0));
+    }
+
+    #[test]
+    fn routh_constants() {
+        let constants: [Poly64; 3] = [poly![], poly![1.0], poly![-3.0]];
+        for p in constants {
+            let routh = p.routh_array();
+            assert!(routh.table.is_empty());
+            assert_eq!(routh.counts, StabilityCounts::default());
+            assert!(p.is_hurwitz_stable());
+        }
+    }
+}